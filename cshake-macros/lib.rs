@@ -0,0 +1,102 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, Ident, LitInt, LitStr};
+use cshake::{cshake_init_const, keccak_state::{BYTES, BITS}, CShakeCustom, NoCustom, R128, R256};
+
+struct Custom {
+    name: Vec<u8>,
+    custom_string: Vec<u8>,
+    // cSHAKE128 or cSHAKE256
+    bits: u16,
+}
+
+fn parse_custom(input: &DeriveInput) -> syn::Result<Custom> {
+    let mut custom = Custom { name: Vec::new(), custom_string: Vec::new(), bits: 256 };
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("cshake")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                custom.name = meta.value()?.parse::<LitStr>()?.value().into_bytes();
+            } else if meta.path.is_ident("custom") {
+                custom.custom_string = meta.value()?.parse::<LitStr>()?.value().into_bytes();
+            } else if meta.path.is_ident("security") {
+                let lit = meta.value()?.parse::<LitInt>()?;
+                custom.bits = lit.base10_parse()?;
+                if custom.bits != 128 && custom.bits != 256 {
+                    return Err(Error::new(lit.span(), "security must be 128 or 256"));
+                }
+            } else {
+                return Err(meta.error("expected `name`, `custom` or `security`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(custom)
+}
+
+fn initial(custom: &Custom) -> [u8; BYTES(BITS)] {
+    let state = match custom.bits {
+        128 => cshake_init_const::<R128>(&custom.name, &custom.custom_string).to_initial(),
+        _ => cshake_init_const::<R256>(&custom.name, &custom.custom_string).to_initial(),
+    };
+    // bytepad always ends on a block boundary
+    state.unwrap()
+}
+
+// two customs with the same name, custom string and security level would share their whole output
+// space, so each one claims a crate-root macro named after a digest of its initial state and the
+// compiler rejects the second definition wherever in the crate it is (E0428)
+// a derive has no crate-local namespace that sees every other expansion, which is why the guard
+// has to be a #[macro_export] macro: it is hidden from the docs but still lands in the public
+// macro namespace of the crate as `__cshake_custom_collision_<digest>`, so two crates can each
+// hold the same custom, and a user macro with such a name would be rejected as a collision too
+fn collision_guard(ident: &Ident, initial: &[u8; BYTES(BITS)]) -> proc_macro2::TokenStream {
+    let digest: String = NoCustom.once_to_array::<16>(initial).iter().map(|b| format!("{:02x}", b)).collect();
+    let guard = Ident::new(&format!("__cshake_custom_collision_{}", digest), ident.span());
+    quote! {
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #guard {
+            () => {};
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let custom = parse_custom(&input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let rate = Ident::new(if custom.bits == 128 { "R128" } else { "R256" }, Span::call_site());
+    let name = syn::LitByteStr::new(&custom.name, Span::call_site());
+    let custom_string = syn::LitByteStr::new(&custom.custom_string, Span::call_site());
+    let initial = initial(&custom);
+    let guard = collision_guard(ident, &initial);
+    Ok(quote! {
+        #guard
+
+        impl #impl_generics ::cshake::CShakeCustom<{ ::cshake::#rate }> for #ident #ty_generics #where_clause {
+            fn name(&self) -> &[u8] {
+                #name
+            }
+
+            fn custom_string(&self) -> &[u8] {
+                #custom_string
+            }
+
+            fn initial(&self) -> ::core::option::Option<&[u8; 200]> {
+                const INITIAL: [u8; 200] = [#(#initial),*];
+                ::core::option::Option::Some(&INITIAL)
+            }
+        }
+    })
+}
+
+// #[derive(CShakeCustom)]
+// #[cshake(name = "...", custom = "...", security = 128)]
+// name and custom default to empty, security to 256, which picks cSHAKE128 or cSHAKE256 and so
+// the rate R128 or R256
+#[proc_macro_derive(CShakeCustom, attributes(cshake))]
+pub fn derive_cshake_custom(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(Error::into_compile_error).into()
+}
@@ -0,0 +1,44 @@
+use cshake::{CShakeCustom, StaticCustom, NoCustom, R128, R256, Absorb, Squeeze};
+use cshake_macros::CShakeCustom;
+
+#[derive(CShakeCustom)]
+#[cshake(custom = "Email Signature")]
+struct EmailSignature;
+
+#[derive(CShakeCustom)]
+#[cshake(name = "N", custom = "Email Signature", security = 128)]
+struct NamedEmailSignature128;
+
+#[derive(CShakeCustom)]
+struct Empty;
+
+// the same strings with another rate are a different custom
+#[derive(CShakeCustom)]
+#[cshake(custom = "Email Signature", security = 128)]
+struct EmailSignature128;
+
+#[test]
+fn test_derive() {
+    let expected = StaticCustom::<R256>::new_with_create_initial(b"", b"Email Signature");
+    assert_eq!(EmailSignature.custom_string(), b"Email Signature");
+    assert_eq!(EmailSignature.initial(), expected.initial());
+    assert_eq!(EmailSignature.once_to_array::<32>(b"abc"), expected.once_to_array::<32>(b"abc"));
+
+    let expected = StaticCustom::<R128>::new(b"N", b"Email Signature", None);
+    assert_eq!(NamedEmailSignature128.name(), b"N");
+    let mut ctx = NamedEmailSignature128.create();
+    ctx.absorb(b"abc");
+    assert_eq!(ctx.squeeze_to_array::<32>(), expected.once_to_array::<32>(b"abc"));
+
+    assert!(Empty.is_empty());
+    assert_eq!(Empty.initial(), Some(&[0; 200]));
+    assert_eq!(Empty.once_to_array::<32>(b"abc"), NoCustom.once_to_array::<32>(b"abc"));
+
+    let expected = StaticCustom::<R128>::new(b"", b"Email Signature", None);
+    assert_eq!(EmailSignature128.once_to_array::<32>(b"abc"), expected.once_to_array::<32>(b"abc"));
+}
+
+#[test]
+fn test_collision() {
+    trybuild::TestCases::new().compile_fail("ui/*.rs");
+}
@@ -0,0 +1,19 @@
+use cshake_macros::CShakeCustom;
+
+mod a {
+    use super::CShakeCustom;
+
+    #[derive(CShakeCustom)]
+    #[cshake(custom = "Email Signature")]
+    pub struct Signature;
+}
+
+mod b {
+    use super::CShakeCustom;
+
+    #[derive(CShakeCustom)]
+    #[cshake(custom = "Email Signature")]
+    pub struct Signature;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use cshake_macros::CShakeCustom;
+
+#[derive(CShakeCustom)]
+#[cshake(custom = "Email Signature", security = 192)]
+pub struct Signature;
+
+#[derive(CShakeCustom)]
+#[cshake(custom = "Email Signature", rate = 128)]
+pub struct LegacySignature;
+
+fn main() {}
@@ -0,0 +1,27 @@
+use keccak_state::{ConstKeccakState, DCSHAKE, DSHAKE, R256};
+use crate::{left_encode, bit_len};
+
+// same state as `CShakeCustom::create`, but evaluable in const items
+pub const fn cshake_init_const<const R: usize>(name: &[u8], custom_string: &[u8]) -> ConstKeccakState<R> {
+    if name.is_empty() && custom_string.is_empty() {
+        return ConstKeccakState::new(DSHAKE);
+    }
+    let mut ctx = ConstKeccakState::new(DCSHAKE);
+    // bytepad(encode_string(N) || encode_string(S), R)
+    let w = left_encode(R as u128);
+    let n = left_encode(bit_len(name.len()));
+    let s = left_encode(bit_len(custom_string.len()));
+    ctx.absorb(w.as_bytes());
+    ctx.absorb(n.as_bytes());
+    ctx.absorb(name);
+    ctx.absorb(s.as_bytes());
+    ctx.absorb(custom_string);
+    let len = w.as_bytes().len() + n.as_bytes().len() + name.len() + s.as_bytes().len() + custom_string.len();
+    ctx.absorb_zero((R - len % R) % R);
+    ctx
+}
+
+// cSHAKE256(input, N * 8, name, custom_string)
+pub const fn cshake_const<const N: usize>(name: &[u8], custom_string: &[u8], input: &[u8]) -> [u8; N] {
+    cshake_init_const::<R256>(name, custom_string).chain_absorb(input).squeeze_to_array()
+}
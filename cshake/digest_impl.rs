@@ -0,0 +1,36 @@
+use digest::{Update, ExtendableOutput, ExtendableOutputReset, HashMarker};
+use keccak_state::DigestReader;
+use crate::{CShake, CShakeCustom, Absorb, Reset};
+
+impl<C: CShakeCustom<R>, const R: usize> Update for CShake<C, R> {
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.absorb(data);
+    }
+}
+
+impl<C: CShakeCustom<R>, const R: usize> ExtendableOutput for CShake<C, R> {
+    type Reader = DigestReader<CShake<C, R>>;
+
+    #[inline(always)]
+    fn finalize_xof(self) -> Self::Reader {
+        DigestReader::new(self)
+    }
+}
+
+impl<C: CShakeCustom<R> + Clone, const R: usize> ExtendableOutputReset for CShake<C, R> {
+    fn finalize_xof_reset(&mut self) -> Self::Reader {
+        let reader = DigestReader::new(self.clone());
+        Reset::reset(self);
+        reader
+    }
+}
+
+impl<C: CShakeCustom<R>, const R: usize> digest::Reset for CShake<C, R> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+}
+
+impl<C: CShakeCustom<R>, const R: usize> HashMarker for CShake<C, R> {}
@@ -0,0 +1,124 @@
+use core::ops::Deref;
+use crate::{Absorb, AbsorbZero};
+
+// 16 bytes of big-endian value plus the length byte
+const ENCODED_MAX: usize = 17;
+
+#[derive(Clone, Copy)]
+pub struct Encoded {
+    buf: [u8; ENCODED_MAX],
+    len: usize,
+}
+
+impl Deref for Encoded {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl Encoded {
+    // the `Deref` target, usable in const contexts
+    #[inline(always)]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.buf.split_at(self.len).0
+    }
+}
+
+impl AsRef<[u8]> for Encoded {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+// big-endian x without leading zeros, at least one byte
+const fn be_trimmed(x: u128) -> ([u8; 16], usize) {
+    let n = if x == 0 { 1 } else { 16 - (x.leading_zeros() / 8) as usize };
+    (x.to_be_bytes(), n)
+}
+
+pub const fn left_encode(x: u128) -> Encoded {
+    let (be, n) = be_trimmed(x);
+    let mut buf = [0; ENCODED_MAX];
+    buf[0] = n as u8;
+    let mut i = 0;
+    while i < n {
+        buf[1 + i] = be[16 - n + i];
+        i += 1;
+    }
+    Encoded { buf, len: n + 1 }
+}
+
+pub const fn right_encode(x: u128) -> Encoded {
+    let (be, n) = be_trimmed(x);
+    let mut buf = [0; ENCODED_MAX];
+    let mut i = 0;
+    while i < n {
+        buf[i] = be[16 - n + i];
+        i += 1;
+    }
+    buf[n] = n as u8;
+    Encoded { buf, len: n + 1 }
+}
+
+// bit length of a byte string, cannot overflow for any in-memory length
+#[inline(always)]
+pub const fn bit_len(len: usize) -> u128 {
+    (len as u128) * 8
+}
+
+// byte length of encode_string(s) for a string of len bytes
+pub const fn encode_string_len(len: usize) -> usize {
+    left_encode(bit_len(len)).len + len
+}
+
+pub trait AbsorbEncode: Absorb {
+    #[inline]
+    fn absorb_left_encode(&mut self, x: u128) {
+        self.absorb(&left_encode(x));
+    }
+
+    #[inline]
+    fn absorb_right_encode(&mut self, x: u128) {
+        self.absorb(&right_encode(x));
+    }
+
+    #[inline]
+    fn absorb_encode_string(&mut self, s: &[u8]) {
+        self.absorb_left_encode(bit_len(s.len()));
+        self.absorb(s);
+    }
+
+    // bytepad(X, w): everything absorbed into the returned wrapper is X
+    fn absorb_bytepad(&mut self, w: usize) -> Bytepad<'_, Self> where Self: AbsorbZero {
+        assert!(w != 0, "bytepad width cannot be equal 0");
+        let mut pad = Bytepad { ctx: self, w, len: 0 };
+        pad.absorb_left_encode(w as u128);
+        pad
+    }
+}
+
+impl<T: Absorb> AbsorbEncode for T {}
+
+pub struct Bytepad<'a, T: Absorb + AbsorbZero> {
+    ctx: &'a mut T,
+    w: usize,
+    // absorbed length modulo w
+    len: usize,
+}
+
+impl<'a, T: Absorb + AbsorbZero> Bytepad<'a, T> {
+    pub fn finish(self) {
+        self.ctx.absorb_zero((self.w - self.len) % self.w);
+    }
+}
+
+impl<'a, T: Absorb + AbsorbZero> Absorb for Bytepad<'a, T> {
+    fn absorb(&mut self, input: &[u8]) {
+        self.ctx.absorb(input);
+        self.len = (self.len + input.len() % self.w) % self.w;
+    }
+}
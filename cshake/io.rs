@@ -0,0 +1,175 @@
+use std::io::{self, Read, BufRead, Write};
+use crate::{CShake, CShakeCustom, Kmac, ParallelHash, Absorb, Squeeze};
+
+// `io::Write` cannot be implemented for every `Absorb` here, so foreign types go through this
+pub struct AbsorbWriter<T: Absorb>(pub T);
+
+impl<T: Absorb> AbsorbWriter<T> {
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Absorb> Write for AbsorbWriter<T> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.absorb(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.absorb(buf);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// endless keystream, every read fills the whole buffer
+pub struct SqueezeReader<T: Squeeze>(pub T);
+
+impl<T: Squeeze> SqueezeReader<T> {
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Squeeze> Read for SqueezeReader<T> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.squeeze(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.0.squeeze(buf);
+        Ok(())
+    }
+}
+
+macro_rules! impl_write {
+    ($($ty:ty => [$($generics:tt)*])*) => {$(
+        impl<$($generics)*> Write for $ty {
+            #[inline]
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.absorb(buf);
+                Ok(buf.len())
+            }
+
+            #[inline]
+            fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+                self.absorb(buf);
+                Ok(())
+            }
+
+            #[inline(always)]
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_write! {
+    CShake<C, R> => [C: CShakeCustom<R>, const R: usize]
+    Kmac<S, R> => [S: AsRef<[u8]>, const R: usize]
+    ParallelHash<S, R> => [S: AsRef<[u8]>, const R: usize]
+}
+
+const READ_BUF_LEN: usize = 8192;
+
+pub trait AbsorbRead: Absorb {
+    // absorbs until EOF, returns the number of bytes absorbed
+    fn absorb_reader<R: Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<u64> {
+        let mut buf = [0; READ_BUF_LEN];
+        let mut total = 0;
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Ok(total),
+                Ok(len) => {
+                    self.absorb(&buf[..len]);
+                    total += len as u64;
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    // same as `absorb_reader`, but absorbs straight from the reader's buffer
+    fn absorb_buf_reader<R: BufRead + ?Sized>(&mut self, reader: &mut R) -> io::Result<u64> {
+        let mut total = 0;
+        loop {
+            let len = match reader.fill_buf() {
+                Ok([]) => return Ok(total),
+                Ok(buf) => {
+                    self.absorb(buf);
+                    buf.len()
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            reader.consume(len);
+            total += len as u64;
+        }
+    }
+}
+
+impl<T: Absorb> AbsorbRead for T {}
+
+// hashes everything read from or written to the inner stream
+pub struct Tee<I, H: Absorb> {
+    inner: I,
+    hasher: H,
+}
+
+impl<I, H: Absorb> Tee<I, H> {
+    #[inline(always)]
+    pub fn new(inner: I, hasher: H) -> Self {
+        Tee { inner, hasher }
+    }
+
+    #[inline(always)]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    #[inline(always)]
+    pub fn into_parts(self) -> (I, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<I: Read, H: Absorb> Read for Tee<I, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.hasher.absorb(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<I: Write, H: Absorb> Write for Tee<I, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.hasher.absorb(&buf[..len]);
+        Ok(len)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "futures-io")]
+mod futures_io;
+
+#[cfg(feature = "tokio")]
+mod tokio_io;
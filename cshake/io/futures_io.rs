@@ -0,0 +1,58 @@
+use core::{pin::Pin, task::{Context, Poll}};
+use std::io;
+use futures_io::{AsyncRead, AsyncWrite};
+use crate::{Absorb, Squeeze};
+use super::{AbsorbWriter, SqueezeReader, Tee};
+
+// the sponge never blocks, so these are always ready
+impl<T: Absorb + Unpin> AsyncWrite for AbsorbWriter<T> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.get_mut().0.absorb(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: Squeeze + Unpin> AsyncRead for SqueezeReader<T> {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.get_mut().0.squeeze(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+}
+
+impl<I: AsyncRead + Unpin, H: Absorb + Unpin> AsyncRead for Tee<I, H> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(len)) = poll {
+            this.hasher.absorb(&buf[..len]);
+        }
+        poll
+    }
+}
+
+impl<I: AsyncWrite + Unpin, H: Absorb + Unpin> AsyncWrite for Tee<I, H> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(len)) = poll {
+            this.hasher.absorb(&buf[..len]);
+        }
+        poll
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{CShake, FunctionCustom, FunctionXof, Absorb, AbsorbEncode, bit_len, Squeeze, R128, R256};
+
+fn ct_eq_acc(diff: &mut u8, a: &[u8], b: &[u8]) {
+    for (x, y) in a.iter().zip(b) {
+        *diff |= x ^ y;
+    }
+}
+
+// SP 800-185 recommends L >= 32 bits, shorter tags are too easy to guess
+pub const KMAC_MIN_TAG_LEN: usize = 4;
+
+#[derive(Clone)]
+pub struct Kmac<S: AsRef<[u8]>, const R: usize> {
+    ctx: CShake<FunctionCustom<S>, R>,
+}
+
+impl<S: AsRef<[u8]>, const R: usize> Kmac<S, R> {
+    pub fn new(key: &[u8], custom_string: S) -> Self {
+        let mut ctx = CShake::create(FunctionCustom { name: b"KMAC", custom_string });
+        let mut pad = ctx.absorb_bytepad(R);
+        pad.absorb_encode_string(key);
+        pad.finish();
+        Kmac { ctx }
+    }
+
+    pub fn finalize(mut self, output: &mut [u8]) {
+        self.ctx.absorb_right_encode(bit_len(output.len()));
+        self.ctx.squeeze(output);
+    }
+
+    pub fn finalize_to_array<const N: usize>(mut self) -> [u8; N] {
+        self.ctx.absorb_right_encode(bit_len(N));
+        self.ctx.squeeze_to_array()
+    }
+
+    pub fn finalize_xof(mut self) -> KmacXof<S, R> {
+        self.ctx.absorb_right_encode(0);
+        FunctionXof::new(self.ctx)
+    }
+
+    // constant-time with respect to the tag contents, tags shorter than `KMAC_MIN_TAG_LEN` never verify
+    pub fn verify(mut self, tag: &[u8]) -> bool {
+        if tag.len() < KMAC_MIN_TAG_LEN {
+            return false;
+        }
+        self.ctx.absorb_right_encode(bit_len(tag.len()));
+        let mut diff = 0;
+        let mut buf = [0; 64];
+        for chunk in tag.chunks(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            self.ctx.squeeze(buf);
+            ct_eq_acc(&mut diff, buf, chunk);
+        }
+        core::hint::black_box(diff) == 0
+    }
+}
+
+impl<S: AsRef<[u8]>, const R: usize> Absorb for Kmac<S, R> {
+    #[inline(always)]
+    fn absorb(&mut self, input: &[u8]) {
+        self.ctx.absorb(input);
+    }
+}
+
+pub type KmacXof<S, const R: usize> = FunctionXof<S, R>;
+
+pub type Kmac128<S> = Kmac<S, R128>;
+pub type Kmac256<S> = Kmac<S, R256>;
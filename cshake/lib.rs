@@ -7,54 +7,46 @@
 #[cfg(feature = "zeroize-on-drop")] use zeroize::Zeroize;
 pub use keccak_state::{self, Absorb, AbsorbZero, Squeeze, SqueezeXor, SqueezeSkip, Reset};
 #[cfg(feature = "seed")] pub use keccak_state::AbsorbSeed;
-use keccak_state::{KeccakState, KeccakF, R256, DCSHAKE, DSHAKE, BYTES, BITS, Foldable, IOBuf, Switch};
+pub use keccak_state::{R128, R256};
+use keccak_state::{KeccakState, KeccakF, DCSHAKE, DSHAKE, BYTES, BITS, SNAPSHOT_LEN, Foldable, IOBuf, Switch};
 
 // region: encode len
 
+mod encode;
+pub use encode::{Encoded, Bytepad, AbsorbEncode, left_encode, right_encode, bit_len, encode_string_len};
+
 pub trait AbsorbLenLeft: Absorb {
+    #[inline]
     fn absorb_len_left(&mut self, len: usize) {
-        if len == 0 {
-            self.absorb(&[1, 0]);
-        } else {
-            let lz = len.leading_zeros() / 8;
-            let len = len.to_be_bytes();
-            self.absorb(&[(core::mem::size_of::<usize>() as u8) - (lz as u8)]);
-            self.absorb(&len[lz as usize..]);
-        }
+        self.absorb_left_encode(len as u128);
     }
 }
 
 impl<T: Absorb> AbsorbLenLeft for T {}
 
-#[cfg(feature = "right-encode")]
 pub trait AbsorbLenRight: Absorb {
+    #[inline]
     fn absorb_len_right(&mut self, len: usize) {
-        if len == 0 {
-            self.absorb(&[0, 1]);
-        } else {
-            let lz = len.leading_zeros() / 8;
-            let len = len.to_be_bytes();
-            self.absorb(&len[lz as usize..]);
-            self.absorb(&[(core::mem::size_of::<usize>() as u8) - (lz as u8)]);
-        }
+        self.absorb_right_encode(len as u128);
     }
 }
 
-#[cfg(feature = "right-encode")]
 impl<T: Absorb> AbsorbLenRight for T {}
 
 // endregion
 
 // region: state
 
-const R: usize = R256;
-
-pub struct CShake<C: CShakeCustom> {
+#[derive(Clone)]
+pub struct CShake<C: CShakeCustom<R>, const R: usize = R256> {
     ctx: KeccakState<KeccakF, R>,
     custom: C,
 }
 
-impl<C: CShakeCustom> CShake<C> {
+pub type CShake128<C> = CShake<C, R128>;
+pub type CShake256<C> = CShake<C, R256>;
+
+impl<C: CShakeCustom<R>, const R: usize> CShake<C, R> {
     #[inline(always)]
     pub fn custom(&self) -> &C {
         &self.custom
@@ -62,12 +54,10 @@ impl<C: CShakeCustom> CShake<C> {
 
     fn init(&mut self) {
         if !self.custom.is_empty() {
-            self.ctx.absorb_len_left(R);
-            self.ctx.absorb_len_left(self.custom.name().len() * 8);
-            self.ctx.absorb(self.custom.name());
-            self.ctx.absorb_len_left(self.custom.custom_string().len() * 8);
-            self.ctx.absorb(self.custom.custom_string());
-            self.ctx.fill_block();
+            let mut pad = self.ctx.absorb_bytepad(R);
+            pad.absorb_encode_string(self.custom.name());
+            pad.absorb_encode_string(self.custom.custom_string());
+            pad.finish();
         }
     }
 
@@ -75,12 +65,26 @@ impl<C: CShakeCustom> CShake<C> {
         CShake { ctx: KeccakState::with_initial(custom.delim(), initial), custom }
     }
 
-    pub fn create(custom: C) -> CShake<C> {
+    pub fn create(custom: C) -> Self {
         let mut _self = CShake { ctx: KeccakState::new(custom.delim()), custom };
         _self.init();
         _self
     }
 
+    #[inline]
+    pub fn export(&self) -> [u8; SNAPSHOT_LEN] {
+        self.ctx.export()
+    }
+
+    // the custom itself is not part of the snapshot, only its delimiter can be checked
+    pub fn import(custom: C, buf: &[u8]) -> Option<Self> {
+        let ctx = KeccakState::import(buf)?;
+        if ctx.delim() != custom.delim() {
+            return None;
+        }
+        Some(CShake { ctx, custom })
+    }
+
     pub fn squeeze_to_ctx<const N: usize, C2: CShakeCustom>(&mut self, custom: C2) -> CShake<C2> {
         #[allow(unused_mut)]
         let mut buf = self.squeeze_to_array::<N>();
@@ -95,7 +99,7 @@ impl<C: CShakeCustom> CShake<C> {
 
 // region: trait impls
 
-impl<C: CShakeCustom> Foldable for CShake<C> {
+impl<C: CShakeCustom<R>, const R: usize> Foldable for CShake<C, R> {
     #[inline(always)]
     fn fold<B: IOBuf>(&mut self, iobuf: &mut B) {
         self.ctx.fold(iobuf)
@@ -107,14 +111,14 @@ impl<C: CShakeCustom> Foldable for CShake<C> {
     }
 }
 
-impl<C: CShakeCustom> Switch for CShake<C> {
+impl<C: CShakeCustom<R>, const R: usize> Switch for CShake<C, R> {
     #[inline(always)]
     fn switch<const M: bool>(&mut self) {
         self.ctx.switch::<M>()
     }
 }
 
-impl<C: CShakeCustom> Reset for CShake<C> {
+impl<C: CShakeCustom<R>, const R: usize> Reset for CShake<C, R> {
     fn reset(&mut self) {
         self.ctx.reset();
         self.init();
@@ -125,8 +129,12 @@ impl<C: CShakeCustom> Reset for CShake<C> {
 
 // region: custom
 
-pub trait CShakeCustom: Sized {
-    /* const? */ fn rate(&self) -> usize { R256 }
+// the rate is only carried by R, so a custom cannot disagree with the cSHAKE it creates
+pub trait CShakeCustom<const R: usize = R256>: Sized {
+    // kept so that existing overrides still compile, the value is never read
+    #[deprecated = "the rate of a custom is its `R` parameter, overriding this has no effect"]
+    fn rate(&self) -> usize { R }
+
     fn name(&self) -> &[u8] { &[] }
     fn custom_string(&self) -> &[u8];
     fn initial(&self) -> Option<&[u8; BYTES(BITS)]> { None }
@@ -142,7 +150,7 @@ pub trait CShakeCustom: Sized {
     }
 
     #[inline]
-    fn create(self) -> CShake<Self> {
+    fn create(self) -> CShake<Self, R> {
         if let Some(initial) = self.initial() {
             let initial = *initial;
             CShake::create_with_initial(self, initial)
@@ -162,16 +170,26 @@ pub trait CShakeCustom: Sized {
     }
 }
 
+#[derive(Clone, Copy, Default)]
 pub struct NoCustom;
 
 impl CShakeCustom for NoCustom {
     fn custom_string(&self) -> &'static [u8] { &[] }
 }
 
+// `NoCustom` at the cSHAKE128 rate, i.e. SHAKE128
+#[derive(Clone, Copy, Default)]
+pub struct NoCustom128;
+
+impl CShakeCustom<R128> for NoCustom128 {
+    fn custom_string(&self) -> &'static [u8] { &[] }
+}
+
 #[macro_export]
 macro_rules! cshake_customs {
     ($prefix:literal $($name:ident)*) => {$(
         #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy, Default)]
         pub struct $name;
 
         impl $crate::CShakeCustom for $name {
@@ -182,6 +200,7 @@ macro_rules! cshake_customs {
     )*};
     ($($name:ident -> $custom:literal)*) => {$(
         #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy, Default)]
         pub struct $name;
 
         impl $crate::CShakeCustom for $name {
@@ -193,16 +212,16 @@ macro_rules! cshake_customs {
 }
 
 mod static_custom {
-    use crate::{CShake, CShakeCustom, BYTES, BITS};
+    use crate::{CShake, CShakeCustom, cshake_init_const, BYTES, BITS, R256};
 
     #[derive(Clone)]
-    pub struct StaticCustom {
+    pub struct StaticCustom<const R: usize = R256> {
         name: &'static [u8],
         custom_string: &'static [u8],
         initial: Option<[u8; BYTES(BITS)]>,
     }
 
-    impl StaticCustom {
+    impl<const R: usize> StaticCustom<R> {
         pub const fn new(
             name: &'static [u8],
             custom_string: &'static [u8],
@@ -211,6 +230,15 @@ mod static_custom {
             StaticCustom { name, custom_string, initial }
         }
 
+        // computes the initial state at compile time when used in a const item
+        pub const fn precomputed(
+            name: &'static [u8],
+            custom_string: &'static [u8],
+        ) -> Self {
+            let initial = cshake_init_const::<R>(name, custom_string).to_initial();
+            StaticCustom { name, custom_string, initial }
+        }
+
         pub fn new_with_create_initial(
             name: &'static [u8],
             custom_string: &'static [u8],
@@ -218,18 +246,18 @@ mod static_custom {
             let _self = StaticCustom { name, custom_string, initial: None };
             let CShake { ctx, custom: mut _self } = _self.create();
             let initial = ctx.to_initial().unwrap();
-            let _ = core::mem::replace(&mut _self.initial, Some(initial));
+            let _ = _self.initial.replace(initial);
             _self
         }
     }
 
-    impl CShakeCustom for StaticCustom {
+    impl<const R: usize> CShakeCustom<R> for StaticCustom<R> {
         fn name(&self) -> &[u8] {
-            self.name.as_ref()
+            self.name
         }
 
         fn custom_string(&self) -> &[u8] {
-            self.custom_string.as_ref()
+            self.custom_string
         }
 
         fn initial(&self) -> Option<&[u8; BYTES(BITS)]> {
@@ -241,16 +269,16 @@ mod static_custom {
 pub use static_custom::StaticCustom;
 
 mod array_custom {
-    use crate::{CShake, CShakeCustom, BYTES, BITS};
+    use crate::{CShake, CShakeCustom, cshake_init_const, BYTES, BITS, R256};
 
     #[derive(Clone)]
-    pub struct ArrayCustom<const L1: usize, const L2: usize> {
+    pub struct ArrayCustom<const L1: usize, const L2: usize, const R: usize = R256> {
         name: [u8; L1],
         custom_string: [u8; L2],
         initial: Option<[u8; BYTES(BITS)]>,
     }
 
-    impl<const L1: usize, const L2: usize> ArrayCustom<L1, L2> {
+    impl<const L1: usize, const L2: usize, const R: usize> ArrayCustom<L1, L2, R> {
         pub const fn new(
             name: [u8; L1],
             custom_string: [u8; L2],
@@ -259,6 +287,15 @@ mod array_custom {
             ArrayCustom { name, custom_string, initial }
         }
 
+        // computes the initial state at compile time when used in a const item
+        pub const fn precomputed(
+            name: [u8; L1],
+            custom_string: [u8; L2],
+        ) -> Self {
+            let initial = cshake_init_const::<R>(&name, &custom_string).to_initial();
+            ArrayCustom { name, custom_string, initial }
+        }
+
         pub fn new_with_create_initial(
             name: [u8; L1],
             custom_string: [u8; L2],
@@ -266,12 +303,12 @@ mod array_custom {
             let _self = ArrayCustom { name, custom_string, initial: None };
             let CShake { ctx, custom: mut _self } = _self.create();
             let initial = ctx.to_initial().unwrap();
-            let _ = core::mem::replace(&mut _self.initial, Some(initial));
+            let _ = _self.initial.replace(initial);
             _self
         }
     }
 
-    impl<const L1: usize, const L2: usize> CShakeCustom for ArrayCustom<L1, L2> {
+    impl<const L1: usize, const L2: usize, const R: usize> CShakeCustom<R> for ArrayCustom<L1, L2, R> {
         fn name(&self) -> &[u8] {
             self.name.as_ref()
         }
@@ -288,19 +325,22 @@ mod array_custom {
 
 pub use array_custom::ArrayCustom;
 
+mod const_init;
+pub use const_init::{cshake_init_const, cshake_const};
+
 #[cfg(feature = "alloc")]
 mod owned_custom {
     use alloc::sync::Arc;
-    use crate::{CShake, CShakeCustom, BYTES, BITS};
+    use crate::{CShake, CShakeCustom, BYTES, BITS, R256};
 
     #[derive(Clone)]
-    pub struct OwnedCustom {
+    pub struct OwnedCustom<const R: usize = R256> {
         name: Option<Arc<[u8]>>,
         custom_string: Option<Arc<[u8]>>,
         initial: Option<Arc<[u8; BYTES(BITS)]>>,
     }
 
-    impl OwnedCustom {
+    impl<const R: usize> OwnedCustom<R> {
         pub fn new(
             name: Option<&[u8]>,
             custom_string: Option<&[u8]>,
@@ -309,7 +349,7 @@ mod owned_custom {
             OwnedCustom {
                 name: name.map(From::from),
                 custom_string: custom_string.map(From::from),
-                initial: initial.map(Clone::clone).map(From::from),
+                initial: initial.copied().map(From::from),
             }
         }
 
@@ -324,12 +364,12 @@ mod owned_custom {
             };
             let CShake { ctx, custom: mut _self } = _self.create();
             let initial = ctx.to_initial().unwrap();
-            let _ = core::mem::replace(&mut _self.initial, Some(Arc::new(initial)));
+            let _ = _self.initial.replace(Arc::new(initial));
             _self
         }
     }
 
-    impl CShakeCustom for OwnedCustom {
+    impl<const R: usize> CShakeCustom<R> for OwnedCustom<R> {
         fn name(&self) -> &[u8] {
             self.name.as_deref().unwrap_or(&[])
         }
@@ -347,6 +387,81 @@ mod owned_custom {
 #[cfg(feature = "alloc")]
 pub use owned_custom::OwnedCustom;
 
+mod function_custom {
+    use core::mem::MaybeUninit;
+    use crate::{CShake, CShakeCustom, Squeeze, SqueezeXor, SqueezeSkip};
+
+    // name fixed by a SP 800-185 function, customization string from the caller
+    #[derive(Clone)]
+    pub struct FunctionCustom<S: AsRef<[u8]>> {
+        pub(crate) name: &'static [u8],
+        pub(crate) custom_string: S,
+    }
+
+    impl<S: AsRef<[u8]>, const R: usize> CShakeCustom<R> for FunctionCustom<S> {
+        fn name(&self) -> &[u8] {
+            self.name
+        }
+
+        fn custom_string(&self) -> &[u8] {
+            self.custom_string.as_ref()
+        }
+    }
+
+    // output stage of a SP 800-185 function finalized in XOF mode
+    pub struct FunctionXof<S: AsRef<[u8]>, const R: usize> {
+        ctx: CShake<FunctionCustom<S>, R>,
+    }
+
+    impl<S: AsRef<[u8]>, const R: usize> FunctionXof<S, R> {
+        pub(crate) fn new(ctx: CShake<FunctionCustom<S>, R>) -> Self {
+            FunctionXof { ctx }
+        }
+    }
+
+    impl<S: AsRef<[u8]>, const R: usize> Squeeze for FunctionXof<S, R> {
+        #[inline(always)]
+        fn squeeze(&mut self, output: &mut [u8]) {
+            self.ctx.squeeze(output);
+        }
+
+        #[inline(always)]
+        fn squeeze_uninit<'b>(&mut self, output: &'b mut [MaybeUninit<u8>]) -> &'b mut [u8] {
+            self.ctx.squeeze_uninit(output)
+        }
+    }
+
+    impl<S: AsRef<[u8]>, const R: usize> SqueezeXor for FunctionXof<S, R> {
+        #[inline(always)]
+        fn squeeze_xor(&mut self, output: &mut [u8]) {
+            self.ctx.squeeze_xor(output);
+        }
+    }
+
+    impl<S: AsRef<[u8]>, const R: usize> SqueezeSkip for FunctionXof<S, R> {
+        #[inline(always)]
+        fn squeeze_skip(&mut self, len: usize) {
+            self.ctx.squeeze_skip(len);
+        }
+    }
+}
+
+use function_custom::FunctionCustom;
+pub use function_custom::FunctionXof;
+
+// endregion
+
+// region: sp800-185
+
+mod kmac;
+pub use kmac::{Kmac, Kmac128, Kmac256, KmacXof, KMAC_MIN_TAG_LEN};
+
+mod tuple_hash;
+pub use tuple_hash::{TupleHash, TupleHash128, TupleHash256, TupleHashXof};
+
+mod parallel_hash;
+pub use parallel_hash::{ParallelHash, ParallelHash128, ParallelHash256, ParallelHashXof};
+
 // endregion
 
 #[cfg(feature = "rand")]
@@ -442,7 +557,93 @@ pub mod rand {
 
     #[cfg(feature = "std")]
     pub use thread::*;
+
+    #[cfg(feature = "rand_core")]
+    mod seeded {
+        use crate::{CShake, CShakeCustom, NoCustom, Absorb, Squeeze};
+        use super::DEFAULT_SEED_LEN;
+
+        // no reseeding, the output stream is fully determined by the seed
+        pub struct SeededRng<C: CShakeCustom = NoCustom> {
+            ctx: CShake<C>,
+        }
+
+        impl<C: CShakeCustom> SeededRng<C> {
+            pub fn from_seed_with_custom(custom: C, seed: [u8; DEFAULT_SEED_LEN]) -> Self {
+                SeededRng { ctx: custom.create().chain_absorb(&seed) }
+            }
+        }
+
+        impl<C: CShakeCustom> Squeeze for SeededRng<C> {
+            #[inline(always)]
+            fn squeeze(&mut self, output: &mut [u8]) {
+                self.ctx.squeeze(output)
+            }
+        }
+    }
+
+    #[cfg(feature = "rand_core")]
+    pub use seeded::SeededRng;
+
+    #[cfg(feature = "rand_core")]
+    mod rng_core {
+        use rand_core::{RngCore, CryptoRng, SeedableRng};
+        use crate::{CShakeCustom, Squeeze};
+        use super::{ReseedableRng, SeededRng, DEFAULT_SEED_LEN};
+
+        // the sponge squeezes byte-granular, so only the requested bytes are consumed
+        // TryRngCore and TryCryptoRng come from the rand_core blanket impls
+        macro_rules! impl_rng_core {
+            ($($ty:ty => [$($generics:tt)*])*) => {$(
+                impl<$($generics)*> RngCore for $ty {
+                    #[inline]
+                    fn next_u32(&mut self) -> u32 {
+                        u32::from_le_bytes(self.squeeze_to_array())
+                    }
+
+                    #[inline]
+                    fn next_u64(&mut self) -> u64 {
+                        u64::from_le_bytes(self.squeeze_to_array())
+                    }
+
+                    #[inline(always)]
+                    fn fill_bytes(&mut self, dst: &mut [u8]) {
+                        self.squeeze(dst)
+                    }
+                }
+
+                impl<$($generics)*> CryptoRng for $ty {}
+            )*};
+        }
+
+        impl_rng_core! {
+            ReseedableRng<C, I, L> => [C: CShakeCustom, const I: usize, const L: usize]
+            SeededRng<C> => [C: CShakeCustom]
+        }
+
+        #[cfg(feature = "std")]
+        impl_rng_core! {
+            super::ThreadRng => []
+        }
+
+        impl<C: CShakeCustom + Default> SeedableRng for SeededRng<C> {
+            type Seed = [u8; DEFAULT_SEED_LEN];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                SeededRng::from_seed_with_custom(C::default(), seed)
+            }
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+pub mod io;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+#[cfg(feature = "digest")]
+mod digest_impl;
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,135 @@
+use keccak_state::Shake;
+use crate::{CShake, FunctionCustom, FunctionXof, Absorb, AbsorbEncode, bit_len, Squeeze, Reset, R128, R256};
+
+// chaining value length, 2c bits
+const fn cv_len(rate: usize) -> usize {
+    200 - rate
+}
+
+const CV_MAX: usize = cv_len(R256);
+
+#[cfg(feature = "std")]
+#[inline]
+fn leaf_cv<const R: usize>(block: &[u8]) -> [u8; CV_MAX] {
+    let mut cv = [0; CV_MAX];
+    Shake::<R>::once(block, &mut cv[..cv_len(R)]);
+    cv
+}
+
+#[derive(Clone)]
+pub struct ParallelHash<S: AsRef<[u8]>, const R: usize> {
+    ctx: CShake<FunctionCustom<S>, R>,
+    leaf: Shake<R>,
+    block_size: usize,
+    leaf_len: usize,
+    blocks: usize,
+}
+
+impl<S: AsRef<[u8]>, const R: usize> ParallelHash<S, R> {
+    // the chaining values have to fit CV_MAX, so a rate below R256 fails to compile
+    const CHECK_RATE: () = assert!(cv_len(R) <= CV_MAX, "rate too small for ParallelHash");
+
+    pub fn new(block_size: usize, custom_string: S) -> Self {
+        let () = Self::CHECK_RATE;
+        assert!(block_size != 0, "block size cannot be equal 0");
+        let mut ctx = CShake::create(FunctionCustom { name: b"ParallelHash", custom_string });
+        ctx.absorb_left_encode(block_size as u128);
+        ParallelHash { ctx, leaf: Shake::new(), block_size, leaf_len: 0, blocks: 0 }
+    }
+
+    fn finish_leaf(&mut self) {
+        let mut cv = [0; CV_MAX];
+        let cv = &mut cv[..cv_len(R)];
+        self.leaf.squeeze(cv);
+        self.ctx.absorb(cv);
+        self.leaf.reset();
+        self.leaf_len = 0;
+        self.blocks += 1;
+    }
+
+    fn finish(&mut self, output_bits: u128) {
+        if self.leaf_len != 0 {
+            self.finish_leaf();
+        }
+        self.ctx.absorb_right_encode(self.blocks as u128);
+        self.ctx.absorb_right_encode(output_bits);
+    }
+
+    pub fn finalize(mut self, output: &mut [u8]) {
+        self.finish(bit_len(output.len()));
+        self.ctx.squeeze(output);
+    }
+
+    pub fn finalize_to_array<const N: usize>(mut self) -> [u8; N] {
+        self.finish(bit_len(N));
+        self.ctx.squeeze_to_array()
+    }
+
+    pub fn finalize_xof(mut self) -> ParallelHashXof<S, R> {
+        self.finish(0);
+        FunctionXof::new(self.ctx)
+    }
+}
+
+impl<S: AsRef<[u8]>, const R: usize> Absorb for ParallelHash<S, R> {
+    fn absorb(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            let len = (self.block_size - self.leaf_len).min(input.len());
+            let (part, rest) = input.split_at(len);
+            self.leaf.absorb(part);
+            self.leaf_len += len;
+            if self.leaf_len == self.block_size {
+                self.finish_leaf();
+            }
+            input = rest;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: AsRef<[u8]>, const R: usize> ParallelHash<S, R> {
+    // leaves processed per thread before their chaining values are absorbed
+    const BATCH: usize = 16;
+
+    pub fn absorb_parallel(&mut self, input: &[u8]) {
+        let threads = std::thread::available_parallelism().map_or(1, core::num::NonZeroUsize::get);
+        self.absorb_parallel_with(input, threads);
+    }
+
+    pub fn absorb_parallel_with(&mut self, mut input: &[u8], threads: usize) {
+        if threads <= 1 {
+            return self.absorb(input);
+        }
+        let block_size = self.block_size;
+        if self.leaf_len != 0 {
+            let (head, rest) = input.split_at((block_size - self.leaf_len).min(input.len()));
+            self.absorb(head);
+            input = rest;
+        }
+        let (blocks, rest) = input.split_at(input.len() / block_size * block_size);
+        let mut cvs = alloc::vec![[0; CV_MAX]; threads * Self::BATCH];
+        for batch in blocks.chunks(cvs.len() * block_size) {
+            let n = batch.len() / block_size;
+            let per_thread = n.div_ceil(threads);
+            std::thread::scope(|scope| {
+                for (cvs, batch) in cvs[..n].chunks_mut(per_thread).zip(batch.chunks(per_thread * block_size)) {
+                    let _ = scope.spawn(move || {
+                        for (cv, block) in cvs.iter_mut().zip(batch.chunks(block_size)) {
+                            *cv = leaf_cv::<R>(block);
+                        }
+                    });
+                }
+            });
+            for cv in &cvs[..n] {
+                self.ctx.absorb(&cv[..cv_len(R)]);
+            }
+            self.blocks += n;
+        }
+        self.absorb(rest);
+    }
+}
+
+pub type ParallelHashXof<S, const R: usize> = FunctionXof<S, R>;
+
+pub type ParallelHash128<S> = ParallelHash<S, R128>;
+pub type ParallelHash256<S> = ParallelHash<S, R256>;
@@ -0,0 +1,138 @@
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+use keccak_state::SerdeBytes;
+use crate::{CShake, CShakeCustom, NoCustom, NoCustom128, StaticCustom, ArrayCustom, BYTES, BITS, SNAPSHOT_LEN};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CShakeCustom")]
+#[serde(bound(
+    serialize = "N: AsRef<[u8]>, S: AsRef<[u8]>",
+    deserialize = "SerdeBytes<N>: Deserialize<'de>, SerdeBytes<S>: Deserialize<'de>",
+))]
+struct CustomRepr<N, S> {
+    rate: usize,
+    name: SerdeBytes<N>,
+    custom_string: SerdeBytes<S>,
+    initial: Option<SerdeBytes<[u8; BYTES(BITS)]>>,
+}
+
+impl<'a> CustomRepr<&'a [u8], &'a [u8]> {
+    fn from_custom<C: CShakeCustom<R>, const R: usize>(custom: &'a C) -> Self {
+        CustomRepr {
+            rate: R,
+            name: SerdeBytes(custom.name()),
+            custom_string: SerdeBytes(custom.custom_string()),
+            initial: custom.initial().copied().map(SerdeBytes),
+        }
+    }
+}
+
+impl<N, S> CustomRepr<N, S> {
+    fn check_rate<E: de::Error, const R: usize>(self) -> Result<Self, E> {
+        if self.rate == R {
+            Ok(self)
+        } else {
+            Err(E::custom("custom rate does not match"))
+        }
+    }
+}
+
+impl Serialize for NoCustom {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit_struct("NoCustom")
+    }
+}
+
+impl<'de> Deserialize<'de> for NoCustom {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "NoCustom")]
+        struct Repr;
+
+        Repr::deserialize(deserializer).map(|Repr| NoCustom)
+    }
+}
+
+impl Serialize for NoCustom128 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit_struct("NoCustom128")
+    }
+}
+
+impl<'de> Deserialize<'de> for NoCustom128 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "NoCustom128")]
+        struct Repr;
+
+        Repr::deserialize(deserializer).map(|Repr| NoCustom128)
+    }
+}
+
+impl<const R: usize> Serialize for StaticCustom<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CustomRepr::from_custom(self).serialize(serializer)
+    }
+}
+
+// the strings are borrowed, so this only works with raw bytes in a 'static input,
+// human-readable formats are rejected, use `ArrayCustom` or `OwnedCustom` there
+impl<'de: 'static, const R: usize> Deserialize<'de> for StaticCustom<R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CustomRepr::<&'static [u8], &'static [u8]>::deserialize(deserializer)?.check_rate::<_, R>()?;
+        Ok(StaticCustom::new(repr.name.0, repr.custom_string.0, repr.initial.map(|x| x.0)))
+    }
+}
+
+impl<const L1: usize, const L2: usize, const R: usize> Serialize for ArrayCustom<L1, L2, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CustomRepr::from_custom(self).serialize(serializer)
+    }
+}
+
+impl<'de, const L1: usize, const L2: usize, const R: usize> Deserialize<'de> for ArrayCustom<L1, L2, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CustomRepr::<[u8; L1], [u8; L2]>::deserialize(deserializer)?.check_rate::<_, R>()?;
+        Ok(ArrayCustom::new(repr.name.0, repr.custom_string.0, repr.initial.map(|x| x.0)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod owned {
+    use alloc::boxed::Box;
+    use serde::{Serialize, Serializer, Deserialize, Deserializer};
+    use crate::OwnedCustom;
+    use super::CustomRepr;
+
+    impl<const R: usize> Serialize for OwnedCustom<R> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            CustomRepr::from_custom(self).serialize(serializer)
+        }
+    }
+
+    impl<'de, const R: usize> Deserialize<'de> for OwnedCustom<R> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let repr = CustomRepr::<Box<[u8]>, Box<[u8]>>::deserialize(deserializer)?.check_rate::<_, R>()?;
+            Ok(OwnedCustom::new(Some(&repr.name.0), Some(&repr.custom_string.0), repr.initial.as_ref().map(|x| &x.0)))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "CShake")]
+struct CShakeRepr<C> {
+    custom: C,
+    state: SerdeBytes<[u8; SNAPSHOT_LEN]>,
+}
+
+impl<C: CShakeCustom<R> + Serialize, const R: usize> Serialize for CShake<C, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CShakeRepr { custom: self.custom(), state: SerdeBytes(self.export()) }.serialize(serializer)
+    }
+}
+
+impl<'de, C: CShakeCustom<R> + Deserialize<'de>, const R: usize> Deserialize<'de> for CShake<C, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let CShakeRepr { custom, state } = CShakeRepr::<C>::deserialize(deserializer)?;
+        CShake::import(custom, &state.0).ok_or_else(|| de::Error::custom("snapshot does not match this cSHAKE"))
+    }
+}
@@ -41,3 +41,502 @@ fn test_cshake256_two() {
     ";
     assert_eq!(expected, &output);
 }
+
+const TEST_CUSTOM_128: StaticCustom<R128> = StaticCustom::new(b"", b"Email Signature", None);
+
+#[test]
+fn test_cshake128_one() {
+    let input = b"\x00\x01\x02\x03";
+    let output = TEST_CUSTOM_128.once_to_array(input);
+    let expected = b"\
+        \xC1\xC3\x69\x25\xB6\x40\x9A\x04\xF1\xB5\x04\xFC\xBC\xA9\xD8\x2B\
+        \x40\x17\x27\x7C\xB5\xED\x2B\x20\x65\xFC\x1D\x38\x14\xD5\xAA\xF5\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_cshake128_two() {
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let output = TEST_CUSTOM_128.once_to_array(&input);
+    let expected = b"\
+        \xC5\x22\x1D\x50\xE4\xF8\x22\xD9\x6A\x2E\x88\x81\xA9\x61\x42\x0F\
+        \x29\x4B\x7B\x24\xFE\x3D\x20\x94\xBA\xED\x2C\x65\x24\xCC\x16\x6B\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_cshake128_initial() {
+    let custom = StaticCustom::<R128>::new_with_create_initial(b"", b"Email Signature");
+    let output: [u8; 32] = CShake128::create(custom).chain_absorb(b"\x00\x01\x02\x03").squeeze_to_array();
+    assert_eq!(output, TEST_CUSTOM_128.once_to_array(b"\x00\x01\x02\x03"));
+}
+
+struct LegacyRateCustom;
+
+impl CShakeCustom for LegacyRateCustom {
+    fn rate(&self) -> usize { R128 }
+    fn custom_string(&self) -> &[u8] { b"Email Signature" }
+}
+
+#[test]
+fn test_cshake_rate_override_ignored() {
+    assert_eq!(LegacyRateCustom.once_to_array::<32>(b"abc"), TEST_CUSTOM.once_to_array::<32>(b"abc"));
+}
+
+#[test]
+fn test_cshake_no_custom() {
+    use keccak_state::{KeccakState, KeccakF, DSHAKE};
+    let input = b"\x00\x01\x02\x03";
+    let output: [u8; 32] = NoCustom128.create().chain_absorb(input).squeeze_to_array();
+    assert_eq!(output, KeccakState::<KeccakF, R128>::new(DSHAKE).chain_absorb(input).squeeze_to_array());
+    let output: [u8; 32] = NoCustom.create().chain_absorb(input).squeeze_to_array();
+    assert_eq!(output, KeccakState::<KeccakF, R256>::new(DSHAKE).chain_absorb(input).squeeze_to_array());
+}
+
+#[test]
+fn test_encode() {
+    assert_eq!(&*left_encode(0), &[1, 0]);
+    assert_eq!(&*right_encode(0), &[0, 1]);
+    assert_eq!(&*left_encode(256), &[2, 1, 0]);
+    assert_eq!(&*right_encode(256), &[1, 0, 2]);
+    assert_eq!(&*left_encode(1 << 64), &[9, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let max = right_encode(u128::MAX);
+    assert_eq!((&max[..16], max[16]), (&[0xFF; 16][..], 16));
+    assert_eq!(bit_len(usize::MAX), (usize::MAX as u128) << 3);
+    assert_eq!(encode_string_len(129), 132);
+}
+
+#[test]
+fn test_cshake_custom_fills_block() {
+    // bytepad(encode_string(N) || encode_string(S), 136) needs no padding
+    const CUSTOM: StaticCustom = StaticCustom::new(b"", &[b'a'; 129], None);
+    let output: [u8; 32] = CUSTOM.once_to_array(b"abc");
+    let expected = b"\
+        \x13\x31\x3F\xBE\xD3\xCD\x6B\x0F\xB8\xA9\x41\xE6\xF9\xB3\xC3\x13\
+        \x9A\xE5\x80\x24\xB7\xEE\x5E\x81\x1B\xB9\xB5\xA8\x2E\x2F\x94\x1A\
+    ";
+    assert_eq!(expected, &output);
+}
+
+const KMAC_KEY: [u8; 32] = *b"\
+    \x40\x41\x42\x43\x44\x45\x46\x47\x48\x49\x4A\x4B\x4C\x4D\x4E\x4F\
+    \x50\x51\x52\x53\x54\x55\x56\x57\x58\x59\x5A\x5B\x5C\x5D\x5E\x5F\
+";
+
+#[test]
+fn test_kmac128() {
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let output = Kmac128::new(&KMAC_KEY, b"").chain_absorb(&input[..4]).finalize_to_array();
+    let expected = b"\
+        \xE5\x78\x0B\x0D\x3E\xA6\xF7\xD3\xA4\x29\xC5\x70\x6A\xA4\x3A\x00\
+        \xFA\xDB\xD7\xD4\x96\x28\x83\x9E\x31\x87\x24\x3F\x45\x6E\xE1\x4E\
+    ";
+    assert_eq!(expected, &output);
+    let output = Kmac128::new(&KMAC_KEY, b"My Tagged Application").chain_absorb(&input[..4]).finalize_to_array();
+    let expected = b"\
+        \x3B\x1F\xBA\x96\x3C\xD8\xB0\xB5\x9E\x8C\x1A\x6D\x71\x88\x8B\x71\
+        \x43\x65\x1A\xF8\xBA\x0A\x70\x70\xC0\x97\x9E\x28\x11\x32\x4A\xA5\
+    ";
+    assert_eq!(expected, &output);
+    let mut output = [0; 32];
+    Kmac128::new(&KMAC_KEY, b"My Tagged Application").chain_absorb(&input).finalize(&mut output);
+    let expected = b"\
+        \x1F\x5B\x4E\x6C\xCA\x02\x20\x9E\x0D\xCB\x5C\xA6\x35\xB8\x9A\x15\
+        \xE2\x71\xEC\xC7\x60\x07\x1D\xFD\x80\x5F\xAA\x38\xF9\x72\x92\x30\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_kmac256() {
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let output = Kmac256::new(&KMAC_KEY, b"My Tagged Application").chain_absorb(&input[..4]).finalize_to_array();
+    let expected = b"\
+        \x20\xC5\x70\xC3\x13\x46\xF7\x03\xC9\xAC\x36\xC6\x1C\x03\xCB\x64\
+        \xC3\x97\x0D\x0C\xFC\x78\x7E\x9B\x79\x59\x9D\x27\x3A\x68\xD2\xF7\
+        \xF6\x9D\x4C\xC3\xDE\x9D\x10\x4A\x35\x16\x89\xF2\x7C\xF6\xF5\x95\
+        \x1F\x01\x03\xF3\x3F\x4F\x24\x87\x10\x24\xD9\xC2\x77\x73\xA8\xDD\
+    ";
+    assert_eq!(expected, &output);
+    let output = Kmac256::new(&KMAC_KEY, b"").chain_absorb(&input).finalize_to_array();
+    let expected = b"\
+        \x75\x35\x8C\xF3\x9E\x41\x49\x4E\x94\x97\x07\x92\x7C\xEE\x0A\xF2\
+        \x0A\x3F\xF5\x53\x90\x4C\x86\xB0\x8F\x21\xCC\x41\x4B\xCF\xD6\x91\
+        \x58\x9D\x27\xCF\x5E\x15\x36\x9C\xBB\xFF\x8B\x9A\x4C\x2E\xB1\x78\
+        \x00\x85\x5D\x02\x35\xFF\x63\x5D\xA8\x25\x33\xEC\x6B\x75\x9B\x69\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_kmac_xof() {
+    let input = b"\x00\x01\x02\x03";
+    let output: [u8; 32] = Kmac128::new(&KMAC_KEY, b"").chain_absorb(input).finalize_xof().squeeze_to_array();
+    let expected = b"\
+        \xCD\x83\x74\x0B\xBD\x92\xCC\xC8\xCF\x03\x2B\x14\x81\xA0\xF4\x46\
+        \x0E\x7C\xA9\xDD\x12\xB0\x8A\x0C\x40\x31\x17\x8B\xAC\xD6\xEC\x35\
+    ";
+    assert_eq!(expected, &output);
+    let output: [u8; 64] = Kmac256::new(&KMAC_KEY, b"My Tagged Application").chain_absorb(input).finalize_xof().squeeze_to_array();
+    let expected = b"\
+        \x17\x55\x13\x3F\x15\x34\x75\x2A\xAD\x07\x48\xF2\xC7\x06\xFB\x5C\
+        \x78\x45\x12\xCA\xB8\x35\xCD\x15\x67\x6B\x16\xC0\xC6\x64\x7F\xA9\
+        \x6F\xAA\x7A\xF6\x34\xA0\xBF\x8F\xF6\xDF\x39\x37\x4F\xA0\x0F\xAD\
+        \x9A\x39\xE3\x22\xA7\xC9\x20\x65\xA6\x4E\xB1\xFB\x08\x01\xEB\x2B\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_kmac_verify() {
+    let mac = Kmac256::new(&KMAC_KEY, b"").chain_absorb(b"message");
+    let tag: [u8; 100] = mac.clone().finalize_to_array();
+    assert!(mac.clone().verify(&tag));
+    assert!(!mac.clone().verify(&tag[..99]));
+    let mut forged = tag;
+    forged[99] ^= 1;
+    assert!(!mac.clone().verify(&forged));
+    assert!(!mac.clone().verify(&[]));
+    let short: [u8; KMAC_MIN_TAG_LEN - 1] = mac.clone().finalize_to_array();
+    assert!(!mac.clone().verify(&short));
+    let min: [u8; KMAC_MIN_TAG_LEN] = mac.clone().finalize_to_array();
+    assert!(mac.verify(&min));
+}
+
+#[test]
+fn test_kmac_key_fills_block() {
+    // bytepad(encode_string(K), 168) is exactly one block
+    let output: [u8; 32] = Kmac128::new(&[0; 163], b"").chain_absorb(b"abc").finalize_to_array();
+    let expected = b"\
+        \xAA\x2D\xB3\xE9\x35\x7C\xBE\x17\xE3\x19\x35\x1C\x6D\x67\xAA\x51\
+        \x4A\xC9\x21\xB6\x6A\x58\x6D\xA8\x8F\x19\xBC\xB6\xF2\x50\x9F\xA2\
+    ";
+    assert_eq!(expected, &output);
+}
+
+const TUPLE: [&[u8]; 3] = [
+    b"\x00\x01\x02",
+    b"\x10\x11\x12\x13\x14\x15",
+    b"\x20\x21\x22\x23\x24\x25\x26\x27\x28",
+];
+
+#[test]
+fn test_tuple_hash128() {
+    let output = TupleHash128::with_tuple(b"", &TUPLE[..2]).finalize_to_array();
+    let expected = b"\
+        \xC5\xD8\x78\x6C\x1A\xFB\x9B\x82\x11\x1A\xB3\x4B\x65\xB2\xC0\x04\
+        \x8F\xA6\x4E\x6D\x48\xE2\x63\x26\x4C\xE1\x70\x7D\x3F\xFC\x8E\xD1\
+    ";
+    assert_eq!(expected, &output);
+    let output = TupleHash128::new(b"My Tuple App").chain_push(TUPLE[0]).chain_push(TUPLE[1]).finalize_to_array();
+    let expected = b"\
+        \x75\xCD\xB2\x0F\xF4\xDB\x11\x54\xE8\x41\xD7\x58\xE2\x41\x60\xC5\
+        \x4B\xAE\x86\xEB\x8C\x13\xE7\xF5\xF4\x0E\xB3\x55\x88\xE9\x6D\xFB\
+    ";
+    assert_eq!(expected, &output);
+    let mut output = [0; 32];
+    TupleHash128::with_tuple(b"My Tuple App", TUPLE).finalize(&mut output);
+    let expected = b"\
+        \xE6\x0F\x20\x2C\x89\xA2\x63\x1E\xDA\x8D\x4C\x58\x8C\xA5\xFD\x07\
+        \xF3\x9E\x51\x51\x99\x8D\xEC\xCF\x97\x3A\xDB\x38\x04\xBB\x6E\x84\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_tuple_hash256() {
+    let output = TupleHash256::with_tuple(b"My Tuple App", TUPLE).finalize_to_array();
+    let expected = b"\
+        \x45\x00\x0B\xE6\x3F\x9B\x6B\xFD\x89\xF5\x47\x17\x67\x0F\x69\xA9\
+        \xBC\x76\x35\x91\xA4\xF0\x5C\x50\xD6\x88\x91\xA7\x44\xBC\xC6\xE7\
+        \xD6\xD5\xB5\xE8\x2C\x01\x8D\xA9\x99\xED\x35\xB0\xBB\x49\xC9\x67\
+        \x8E\x52\x6A\xBD\x8E\x85\xC1\x3E\xD2\x54\x02\x1D\xB9\xE7\x90\xCE\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_tuple_hash_xof() {
+    let output: [u8; 32] = TupleHash128::with_tuple(b"", &TUPLE[..2]).finalize_xof().squeeze_to_array();
+    let expected = b"\
+        \x2F\x10\x3C\xD7\xC3\x23\x20\x35\x34\x95\xC6\x8D\xE1\xA8\x12\x92\
+        \x45\xC6\x32\x5F\x6F\x2A\x3D\x60\x8D\x92\x17\x9C\x96\xE6\x84\x88\
+    ";
+    assert_eq!(expected, &output);
+    let output: [u8; 64] = TupleHash256::with_tuple(b"My Tuple App", TUPLE).finalize_xof().squeeze_to_array();
+    let expected = b"\
+        \x0C\x59\xB1\x14\x64\xF2\x33\x6C\x34\x66\x3E\xD5\x1B\x2B\x95\x0B\
+        \xEC\x74\x36\x10\x85\x6F\x36\xC2\x8D\x1D\x08\x8D\x8A\x24\x46\x28\
+        \x4D\xD0\x98\x30\xA6\xA1\x78\xDC\x75\x23\x76\x19\x9F\xAE\x93\x5D\
+        \x86\xCF\xDE\xE5\x91\x3D\x49\x22\xDF\xD3\x69\xB6\x6A\x53\xC8\x97\
+    ";
+    assert_eq!(expected, &output);
+}
+
+const PARALLEL_INPUT: &[u8] = b"\
+    \x00\x01\x02\x03\x04\x05\x06\x07\x10\x11\x12\x13\x14\x15\x16\x17\
+    \x20\x21\x22\x23\x24\x25\x26\x27\
+";
+
+#[test]
+fn test_parallel_hash128() {
+    let output = ParallelHash128::new(8, b"").chain_absorb(PARALLEL_INPUT).finalize_to_array();
+    let expected = b"\
+        \xBA\x8D\xC1\xD1\xD9\x79\x33\x1D\x3F\x81\x36\x03\xC6\x7F\x72\x60\
+        \x9A\xB5\xE4\x4B\x94\xA0\xB8\xF9\xAF\x46\x51\x44\x54\xA2\xB4\xF5\
+    ";
+    assert_eq!(expected, &output);
+    let mut output = [0; 32];
+    ParallelHash128::new(8, b"Parallel Data").chain_absorb(PARALLEL_INPUT).finalize(&mut output);
+    let expected = b"\
+        \xFC\x48\x4D\xCB\x3F\x84\xDC\xEE\xDC\x35\x34\x38\x15\x1B\xEE\x58\
+        \x15\x7D\x6E\xFE\xD0\x44\x5A\x81\xF1\x65\xE4\x95\x79\x5B\x72\x06\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_parallel_hash256() {
+    let output = ParallelHash256::new(8, b"Parallel Data").chain_absorb(PARALLEL_INPUT).finalize_to_array();
+    let expected = b"\
+        \xCD\xF1\x52\x89\xB5\x4F\x62\x12\xB4\xBC\x27\x05\x28\xB4\x95\x26\
+        \x00\x6D\xD9\xB5\x4E\x2B\x6A\xDD\x1E\xF6\x90\x0D\xDA\x39\x63\xBB\
+        \x33\xA7\x24\x91\xF2\x36\x96\x9C\xA8\xAF\xAE\xA2\x9C\x68\x2D\x47\
+        \xA3\x93\xC0\x65\xB3\x8E\x29\xFA\xE6\x51\xA2\x09\x1C\x83\x31\x10\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_parallel_hash_xof() {
+    let output: [u8; 32] = ParallelHash128::new(8, b"").chain_absorb(PARALLEL_INPUT).finalize_xof().squeeze_to_array();
+    let expected = b"\
+        \xFE\x47\xD6\x61\xE4\x9F\xFE\x5B\x7D\x99\x99\x22\xC0\x62\x35\x67\
+        \x50\xCA\xF5\x52\x98\x5B\x8E\x8C\xE6\x66\x7F\x27\x27\xC3\xC8\xD3\
+    ";
+    assert_eq!(expected, &output);
+}
+
+#[test]
+fn test_parallel_hash_streaming() {
+    let mut ctx = ParallelHash128::new(8, b"");
+    for chunk in PARALLEL_INPUT.chunks(5) {
+        ctx.absorb(chunk);
+    }
+    assert_eq!(ctx.finalize_to_array::<32>(), ParallelHash128::new(8, b"").chain_absorb(PARALLEL_INPUT).finalize_to_array());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_parallel_hash_threads() {
+    let input: std::vec::Vec<u8> = (0..100000).map(|i| (i % 251) as u8).collect();
+    let expected = b"\
+        \xC6\x38\x54\x96\x08\x12\x53\xBD\x6F\x30\xA5\xAB\xB5\x78\x15\xE2\
+        \x98\xAB\x6E\x33\xFF\x28\x7C\x4F\x6D\x60\x49\x8D\x32\x7F\xD0\x47\
+    ";
+    for threads in [1, 2, 3, 8] {
+        let mut ctx = ParallelHash128::new(1000, b"");
+        ctx.absorb(&input[..1234]);
+        ctx.absorb_parallel_with(&input[1234..98765], threads);
+        ctx.absorb(&input[98765..]);
+        assert_eq!(expected, &ctx.finalize_to_array::<32>());
+    }
+    let mut ctx = ParallelHash256::new(8192, b"x");
+    ctx.absorb_parallel(&input);
+    let expected = b"\
+        \x10\x94\x7C\x95\x79\x22\x72\xB2\x0E\xF6\x3D\x84\x4B\xA3\xEE\x69\
+        \xAF\x45\xFC\xB5\xA2\x56\x0E\x2E\x1E\x83\x33\x0A\xBC\xF4\xED\x5D\
+        \x1A\x5F\xFC\xAC\xD4\xCF\xE5\x9B\xF4\xCC\x92\x4D\xB5\x38\xD1\xD0\
+        \x35\xFC\xC2\x5D\x8E\x65\xF4\xB0\xF6\xE6\xF6\x94\xE9\x62\x90\x3A\
+    ";
+    assert_eq!(expected, &ctx.finalize_to_array::<64>());
+}
+
+#[test]
+fn test_cshake_snapshot() {
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let ctx = TEST_CUSTOM.create().chain_absorb(&input[..77]);
+    let snapshot = ctx.export();
+    let output: [u8; 64] = CShake::import(TEST_CUSTOM, &snapshot).unwrap().chain_absorb(&input[77..]).squeeze_to_array();
+    assert_eq!(output, TEST_CUSTOM.once_to_array(&input));
+    assert!(CShake::import(NoCustom, &snapshot).is_none());
+    assert!(CShake128::import(TEST_CUSTOM_128, &snapshot).is_none());
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[test]
+fn test_serde_custom() {
+    let custom = OwnedCustom::<R256>::new_with_create_initial(Some(b"N"), Some(b"Email Signature"));
+    let json = serde_json::to_string(&custom).unwrap();
+    assert!(json.starts_with(r#"{"rate":136,"name":"4e","custom_string":"456d61696c205369676e6174757265","initial":""#));
+    let decoded: OwnedCustom = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.initial(), custom.initial());
+    assert_eq!(decoded.once_to_array::<32>(b"abc"), custom.once_to_array::<32>(b"abc"));
+    assert!(serde_json::from_str::<OwnedCustom<R128>>(&json).is_err());
+
+    let custom = ArrayCustom::<1, 15>::new_with_create_initial(*b"N", *b"Email Signature");
+    let bytes = postcard::to_allocvec(&custom).unwrap();
+    let decoded: ArrayCustom<1, 15> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.initial(), custom.initial());
+    assert!(postcard::from_bytes::<ArrayCustom<2, 15>>(&bytes).is_err());
+
+    let bytes: &'static [u8] = alloc::vec::Vec::leak(postcard::to_allocvec(&TEST_CUSTOM).unwrap());
+    let decoded: StaticCustom = postcard::from_bytes(bytes).unwrap();
+    assert_eq!(decoded.custom_string(), b"Email Signature");
+    assert!(decoded.initial().is_none());
+
+    // hex strings cannot be borrowed, decoding them as the raw bytes would change the domain
+    let json: &'static str = alloc::string::String::leak(serde_json::to_string(&TEST_CUSTOM).unwrap());
+    assert!(serde_json::from_str::<StaticCustom>(json).is_err());
+    let custom = ArrayCustom::<0, 15>::new(*b"", *b"Email Signature", None);
+    let decoded: ArrayCustom<0, 15> = serde_json::from_str(&serde_json::to_string(&custom).unwrap()).unwrap();
+    assert_eq!(decoded.custom_string(), b"Email Signature");
+    assert_eq!(decoded.once_to_array::<32>(b"abc"), TEST_CUSTOM.once_to_array::<32>(b"abc"));
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[test]
+fn test_serde_cshake() {
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let custom = OwnedCustom::<R256>::new(None, Some(b"Email Signature"), None);
+    let ctx = custom.clone().create().chain_absorb(&input[..77]);
+    let json = serde_json::to_string(&ctx).unwrap();
+    let resumed: CShake<OwnedCustom> = serde_json::from_str(&json).unwrap();
+    let output: [u8; 64] = resumed.chain_absorb(&input[77..]).squeeze_to_array();
+    assert_eq!(output, TEST_CUSTOM.once_to_array(&input));
+    let bytes = postcard::to_allocvec(&NoCustom.create().chain_absorb(&input)).unwrap();
+    let resumed: CShake<NoCustom> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(resumed.export(), NoCustom.create().chain_absorb(&input).export());
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_digest_cshake() {
+    use digest::{Update, ExtendableOutput, ExtendableOutputReset, XofReader};
+    fn xof<D: Update + ExtendableOutput>(mut ctx: D, input: &[u8], output: &mut [u8]) {
+        ctx.update(input);
+        ctx.finalize_xof().read(output);
+    }
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let mut output = [0; 64];
+    xof(TEST_CUSTOM.create(), &input, &mut output);
+    assert_eq!(output, TEST_CUSTOM.once_to_array(&input));
+    let mut ctx = TEST_CUSTOM.create();
+    ctx.update(&input);
+    let _ = ctx.finalize_xof_reset();
+    ctx.update(b"\x00\x01\x02\x03");
+    ctx.finalize_xof().read(&mut output);
+    assert_eq!(output, TEST_CUSTOM.once_to_array(b"\x00\x01\x02\x03"));
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn test_rand_core() {
+    use rand_core::{RngCore, CryptoRng, SeedableRng, TryRngCore};
+    use crate::rand::SeededRng;
+    fn draw<G: RngCore + CryptoRng>(rng: &mut G) -> (u32, u64, [u8; 4]) {
+        let mut buf = [0; 4];
+        rng.fill_bytes(&mut buf);
+        (rng.next_u32(), rng.next_u64(), buf)
+    }
+    let expected: [u8; 20] = NoCustom.once_to_array(&[7; 32]);
+    let mut rng = SeededRng::<NoCustom>::from_seed([7; 32]);
+    let (a, b, c) = draw(&mut rng);
+    assert_eq!(c, expected[..4]);
+    assert_eq!(a.to_le_bytes(), expected[4..8]);
+    assert_eq!(b.to_le_bytes(), expected[8..16]);
+    assert_eq!(rng.try_next_u32().unwrap().to_le_bytes(), expected[16..]);
+    let mut rng = crate::rand::ReseedableRng::<NoCustom, 8, 32>::init(NoCustom);
+    let _ = draw(&mut rng);
+    #[cfg(feature = "std")]
+    assert_ne!(draw(&mut crate::rand::thread_rng()), draw(&mut crate::rand::thread_rng()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_io() {
+    use std::io::{self, Read, Write, BufReader};
+    use crate::io::{AbsorbWriter, SqueezeReader, AbsorbRead};
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let expected: [u8; 64] = TEST_CUSTOM.once_to_array(&input);
+
+    let mut ctx = TEST_CUSTOM.create();
+    assert_eq!(io::copy(&mut &input[..], &mut ctx).unwrap(), 200);
+    assert_eq!(ctx.squeeze_to_array::<64>(), expected);
+
+    let mut ctx = TEST_CUSTOM.create();
+    assert_eq!(ctx.absorb_reader(&mut (&input[..77]).chain(&input[77..])).unwrap(), 200);
+    assert_eq!(ctx.squeeze_to_array::<64>(), expected);
+
+    let mut ctx = TEST_CUSTOM.create();
+    assert_eq!(ctx.absorb_buf_reader(&mut BufReader::with_capacity(7, &input[..])).unwrap(), 200);
+    let mut output = [0; 64];
+    SqueezeReader(ctx).read_exact(&mut output).unwrap();
+    assert_eq!(output, expected);
+
+    let mut writer = AbsorbWriter(keccak_state::Shake256::new());
+    writer.write_all(&input).unwrap();
+    assert_eq!(writer.into_inner().squeeze_to_array::<64>(), keccak_state::Shake256::once_to_array::<64>(&input));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_tokio_io() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, duplex};
+    use crate::io::{AbsorbWriter, SqueezeReader, Tee};
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let expected: [u8; 64] = TEST_CUSTOM.once_to_array(&input);
+    let (client, server) = duplex(64);
+    let writer = tokio::spawn(async move {
+        let mut tee = Tee::new(client, TEST_CUSTOM.create());
+        for chunk in input.chunks(33) {
+            tee.write_all(chunk).await.unwrap();
+        }
+        tee.shutdown().await.unwrap();
+        tee.into_parts().1.squeeze_to_array::<64>()
+    });
+    let mut tee = Tee::new(server, TEST_CUSTOM.create());
+    let mut received = AbsorbWriter(TEST_CUSTOM.create());
+    assert_eq!(tokio::io::copy(&mut tee, &mut received).await.unwrap(), 200);
+    assert_eq!(writer.await.unwrap(), expected);
+    assert_eq!(tee.into_parts().1.squeeze_to_array::<64>(), expected);
+    let mut output = [0; 64];
+    let _ = SqueezeReader(received.into_inner()).read_exact(&mut output).await.unwrap();
+    assert_eq!(output, expected);
+}
+
+#[cfg(feature = "futures-io")]
+#[test]
+fn test_futures_io() {
+    use futures::io::{AsyncReadExt, AsyncWriteExt, Cursor, copy};
+    use crate::io::{AbsorbWriter, SqueezeReader, Tee};
+    let input: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let expected: [u8; 64] = TEST_CUSTOM.once_to_array(&input);
+    futures::executor::block_on(async {
+        let mut tee = Tee::new(Cursor::new(&input[..]), TEST_CUSTOM.create());
+        let mut sink = Tee::new(AbsorbWriter(TEST_CUSTOM.create()), TEST_CUSTOM.create());
+        assert_eq!(copy(&mut tee, &mut sink).await.unwrap(), 200);
+        sink.close().await.unwrap();
+        let (writer, mut hasher) = sink.into_parts();
+        assert_eq!(tee.into_parts().1.squeeze_to_array::<64>(), expected);
+        assert_eq!(hasher.squeeze_to_array::<64>(), expected);
+        let mut output = [0; 64];
+        SqueezeReader(writer.into_inner()).read_exact(&mut output).await.unwrap();
+        assert_eq!(output, expected);
+    });
+}
+
+#[test]
+fn test_const_init() {
+    const CUSTOM: StaticCustom = StaticCustom::precomputed(b"", b"My App");
+    const CUSTOM_128: ArrayCustom<1, 129, R128> = ArrayCustom::precomputed(*b"N", [b'a'; 129]);
+    const EMPTY: StaticCustom = StaticCustom::precomputed(b"", b"");
+    const ID: [u8; 32] = cshake_const(b"", b"My App", b"id");
+    assert_eq!(CUSTOM.initial(), StaticCustom::<R256>::new_with_create_initial(b"", b"My App").initial());
+    assert_eq!(CUSTOM_128.initial(), ArrayCustom::<1, 129, R128>::new_with_create_initial(*b"N", [b'a'; 129]).initial());
+    assert_eq!(EMPTY.initial(), Some(&[0; 200]));
+    assert_eq!(ID, StaticCustom::<R256>::new(b"", b"My App", None).once_to_array(b"id"));
+    assert_eq!(cshake_const::<64>(b"", b"", b"abc"), NoCustom.once_to_array::<64>(b"abc"));
+}
@@ -0,0 +1,56 @@
+use crate::{CShake, FunctionCustom, FunctionXof, AbsorbEncode, bit_len, Squeeze, R128, R256};
+
+#[derive(Clone)]
+pub struct TupleHash<S: AsRef<[u8]>, const R: usize> {
+    ctx: CShake<FunctionCustom<S>, R>,
+}
+
+impl<S: AsRef<[u8]>, const R: usize> TupleHash<S, R> {
+    pub fn new(custom_string: S) -> Self {
+        TupleHash { ctx: CShake::create(FunctionCustom { name: b"TupleHash", custom_string }) }
+    }
+
+    pub fn with_tuple<I: IntoIterator<Item = T>, T: AsRef<[u8]>>(custom_string: S, tuple: I) -> Self {
+        let mut _self = Self::new(custom_string);
+        _self.extend(tuple);
+        _self
+    }
+
+    pub fn push(&mut self, element: &[u8]) {
+        self.ctx.absorb_encode_string(element);
+    }
+
+    #[inline(always)]
+    pub fn chain_push(mut self, element: &[u8]) -> Self {
+        self.push(element);
+        self
+    }
+
+    pub fn finalize(mut self, output: &mut [u8]) {
+        self.ctx.absorb_right_encode(bit_len(output.len()));
+        self.ctx.squeeze(output);
+    }
+
+    pub fn finalize_to_array<const N: usize>(mut self) -> [u8; N] {
+        self.ctx.absorb_right_encode(bit_len(N));
+        self.ctx.squeeze_to_array()
+    }
+
+    pub fn finalize_xof(mut self) -> TupleHashXof<S, R> {
+        self.ctx.absorb_right_encode(0);
+        FunctionXof::new(self.ctx)
+    }
+}
+
+impl<S: AsRef<[u8]>, const R: usize, T: AsRef<[u8]>> Extend<T> for TupleHash<S, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, tuple: I) {
+        for element in tuple {
+            self.push(element.as_ref());
+        }
+    }
+}
+
+pub type TupleHashXof<S, const R: usize> = FunctionXof<S, R>;
+
+pub type TupleHash128<S> = TupleHash<S, R128>;
+pub type TupleHash256<S> = TupleHash<S, R256>;
@@ -0,0 +1,185 @@
+use crate::{Lane, WORDS, BITS};
+
+type Lanes = [u64; WORDS(BITS)];
+
+pub(crate) const RC: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// rotation offsets, indexed by x + 5y
+pub(crate) const RHO: [u32; 25] = [
+     0,  1, 62, 28, 27,
+    36, 44,  6, 55, 20,
+     3, 10, 43, 25, 39,
+    41, 45, 15, 21,  8,
+    18,  2, 61, 56, 14,
+];
+
+// destination of lane x + 5y under pi, which is y + 5((2x + 3y) mod 5)
+pub(crate) const PI: [usize; 25] = {
+    let mut pi = [0; 25];
+    let mut i = 0;
+    while i < 25 {
+        let (x, y) = (i % 5, i / 5);
+        pi[i] = y + 5 * ((2 * x + 3 * y) % 5);
+        i += 1;
+    }
+    pi
+};
+
+// Keccak-p[b, n_r] runs the last n_r rounds of Keccak-f[b], whose 12 + 2l round constants are
+// the first ones of Keccak-f[1600] cut down to the lane
+#[inline(always)]
+pub(crate) fn round_constants<L: Lane>(rounds: usize) -> &'static [u64] {
+    assert!(rounds <= L::ROUNDS, "Keccak-p has at most 12 + 2l rounds");
+    &RC[L::ROUNDS - rounds..L::ROUNDS]
+}
+
+// repeats the body with `$i` bound to each lane index as a constant
+macro_rules! for_each_lane {
+    ($i:ident => $body:block) => {
+        for_each_lane!(@ $i $body 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24)
+    };
+    (@ $i:ident $body:block $($n:literal)*) => {
+        $({
+            const $i: usize = $n;
+            $body
+        })*
+    };
+}
+
+pub(crate) mod reference;
+mod complement;
+mod multiway;
+pub(crate) use multiway::LanesX;
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+#[cfg(target_arch = "x86_64")]
+mod avx512;
+
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(cpuid_avx2, "avx2");
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(cpuid_avx512, "avx512f");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    // straight transcription of FIPS 202, the baseline the others are checked against
+    Reference,
+    // unrolled, with the lane complementing transform to save NOTs in chi
+    Complement,
+    // the audited `keccak` crate, which runs the ARMv8 SHA3 instructions when the CPU has them
+    Keccak,
+    Avx2,
+    Avx512,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 5] = [Backend::Reference, Backend::Complement, Backend::Keccak, Backend::Avx2, Backend::Avx512];
+
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Reference | Backend::Complement | Backend::Keccak => true,
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => cpuid_avx2::get(),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => cpuid_avx512::get(),
+            #[cfg(not(target_arch = "x86_64"))]
+            Backend::Avx2 | Backend::Avx512 => false,
+        }
+    }
+
+    // a single state only has five lanes per row, which four-lane AVX2 vectors cover with a
+    // leftover lane and a shuffle per step: the throughput bench measures about 680 ns per
+    // Keccak-f[1600] for AVX2 against 420 ns for complement, so AVX2 is never picked here and has
+    // to be forced, AVX-512 fits a row in one vector and does win
+    pub fn detect() -> Backend {
+        if cfg!(target_arch = "aarch64") {
+            Backend::Keccak
+        } else if Backend::Avx512.is_available() {
+            Backend::Avx512
+        } else {
+            Backend::Complement
+        }
+    }
+
+    // the backend `KeccakState` uses, fixed by a backend-* feature (first listed wins) or detected at runtime
+    #[inline]
+    pub fn active() -> Backend {
+        if cfg!(feature = "backend-reference") {
+            Backend::Reference
+        } else if cfg!(feature = "backend-complement") {
+            Backend::Complement
+        } else if cfg!(feature = "backend-keccak") {
+            Backend::Keccak
+        } else if cfg!(feature = "backend-avx2") {
+            Backend::Avx2
+        } else if cfg!(feature = "backend-avx512") {
+            Backend::Avx512
+        } else {
+            Backend::detect()
+        }
+    }
+
+    pub fn permute(self, lanes: &mut Lanes, rounds: usize) {
+        let rc = round_constants::<u64>(rounds);
+        match self {
+            Backend::Reference => reference::permute(lanes, rc, 64),
+            Backend::Complement => complement::permute(lanes, rc),
+            Backend::Keccak => keccak::p1600(lanes, rounds),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => {
+                assert!(self.is_available(), "AVX2 is not supported by this CPU");
+                // SAFETY: the CPU feature has just been checked
+                unsafe { avx2::permute(lanes, rc) }
+            }
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => {
+                assert!(self.is_available(), "AVX-512 is not supported by this CPU");
+                // SAFETY: the CPU feature has just been checked
+                unsafe { avx512::permute(lanes, rc) }
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            Backend::Avx2 | Backend::Avx512 => panic!("{:?} is only available on x86_64", self),
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn permute(lanes: &mut Lanes, rounds: usize) {
+    Backend::active().permute(lanes, rounds)
+}
+
+// the narrower widths have no dedicated backends
+pub(crate) fn permute_portable<L: Lane>(lanes: &mut [L; 25], rounds: usize) {
+    let mut wide = lanes.map(L::widen);
+    reference::permute(&mut wide, round_constants::<L>(rounds), L::BITS);
+    *lanes = wide.map(L::truncate);
+}
+
+// N interleaved states go through the widest vectors that the CPU and the active backend allow and that
+// divide N, a forced scalar backend permutes each state on its own instead
+pub(crate) fn permute_x<const N: usize>(lanes: &mut LanesX<N>, rounds: usize) {
+    let backend = Backend::active();
+    #[cfg(target_arch = "x86_64")]
+    if !cfg!(feature = "backend-reference") && !cfg!(feature = "backend-complement") && !cfg!(feature = "backend-keccak") {
+        let rc = round_constants::<u64>(rounds);
+        if backend != Backend::Avx2 && N.is_multiple_of(8) && Backend::Avx512.is_available() {
+            // SAFETY: the CPU feature has just been checked
+            return unsafe { multiway::permute_avx512(lanes, rc) };
+        }
+        if N.is_multiple_of(4) && Backend::Avx2.is_available() {
+            // SAFETY: the CPU feature has just been checked
+            return unsafe { multiway::permute_avx2(lanes, rc) };
+        }
+        if N.is_multiple_of(2) {
+            return multiway::permute_sse2(lanes, rc);
+        }
+    }
+    multiway::permute_each(lanes, |lanes| backend.permute(lanes, rounds))
+}
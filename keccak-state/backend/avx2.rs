@@ -0,0 +1,66 @@
+use core::arch::x86_64::*;
+use super::{Lanes, RHO, PI};
+
+// each row y holds x = 0..3 in a vector, x = 4 of every row stays scalar
+
+#[inline(always)]
+unsafe fn rolv(v: __m256i, n: __m256i, n_inv: __m256i) -> __m256i {
+    _mm256_or_si256(_mm256_sllv_epi64(v, n), _mm256_srlv_epi64(v, n_inv))
+}
+
+#[inline(always)]
+unsafe fn row(b: &[u64]) -> __m256i {
+    _mm256_setr_epi64x(b[0] as i64, b[1] as i64, b[2] as i64, b[3] as i64)
+}
+
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn permute(lanes: &mut Lanes, rc: &[u64]) {
+    let mut rows = [_mm256_setzero_si256(); 5];
+    let mut last = [0u64; 5];
+    let mut rho = [_mm256_setzero_si256(); 5];
+    let mut rho_inv = [_mm256_setzero_si256(); 5];
+    for y in 0..5 {
+        rows[y] = _mm256_loadu_si256(lanes[5 * y..].as_ptr().cast());
+        last[y] = lanes[5 * y + 4];
+        let r = &RHO[5 * y..];
+        rho[y] = _mm256_setr_epi64x(r[0] as i64, r[1] as i64, r[2] as i64, r[3] as i64);
+        rho_inv[y] = _mm256_sub_epi64(_mm256_set1_epi64x(64), rho[y]);
+    }
+    for &rc in rc {
+        // theta
+        let c = _mm256_xor_si256(
+            _mm256_xor_si256(_mm256_xor_si256(rows[0], rows[1]), _mm256_xor_si256(rows[2], rows[3])),
+            rows[4],
+        );
+        let c4 = _mm256_set1_epi64x((last[0] ^ last[1] ^ last[2] ^ last[3] ^ last[4]) as i64);
+        // columns x - 1 and x + 1 for x = 0..3, with column 4 blended in
+        let c_prev = _mm256_blend_epi32::<0b00000011>(_mm256_permute4x64_epi64::<0b10_01_00_11>(c), c4);
+        let c_next = _mm256_blend_epi32::<0b11000000>(_mm256_permute4x64_epi64::<0b11_11_10_01>(c), c4);
+        let d = _mm256_xor_si256(c_prev, _mm256_or_si256(_mm256_slli_epi64::<1>(c_next), _mm256_srli_epi64::<63>(c_next)));
+        let d4 = (_mm256_extract_epi64::<3>(c) ^ _mm256_extract_epi64::<0>(c).rotate_left(1)) as u64;
+        // rho, then pi through an in-register spill
+        let mut t = [0u64; 25];
+        for y in 0..5 {
+            _mm256_storeu_si256(t[5 * y..].as_mut_ptr().cast(), rolv(_mm256_xor_si256(rows[y], d), rho[y], rho_inv[y]));
+            t[5 * y + 4] = (last[y] ^ d4).rotate_left(RHO[5 * y + 4]);
+        }
+        let mut b = [0u64; 25];
+        for i in 0..25 {
+            b[PI[i]] = t[i];
+        }
+        // chi, building the shifted rows from scalars
+        for y in 0..5 {
+            let r = &b[5 * y..][..5];
+            let next = _mm256_setr_epi64x(r[1] as i64, r[2] as i64, r[3] as i64, r[4] as i64);
+            let next2 = _mm256_setr_epi64x(r[2] as i64, r[3] as i64, r[4] as i64, r[0] as i64);
+            rows[y] = _mm256_xor_si256(row(r), _mm256_andnot_si256(next, next2));
+            last[y] = r[4] ^ (!r[0] & r[1]);
+        }
+        // iota
+        rows[0] = _mm256_xor_si256(rows[0], _mm256_setr_epi64x(rc as i64, 0, 0, 0));
+    }
+    for y in 0..5 {
+        _mm256_storeu_si256(lanes[5 * y..].as_mut_ptr().cast(), rows[y]);
+        lanes[5 * y + 4] = last[y];
+    }
+}
@@ -0,0 +1,78 @@
+use core::arch::x86_64::*;
+use super::{Lanes, RHO};
+
+// each row y holds x = 0..4 in the low five lanes of a vector, the upper three are don't-care
+const ROW: __mmask8 = 0b11111;
+
+// a ^ b ^ c and a ^ (!b & c) as ternary logic truth tables
+const XOR3: i32 = 0x96;
+const CHI: i32 = 0xD2;
+
+// pi moves lane (x + 3y) mod 5 of old row x into lane x of new row y,
+// gathered as rows 0/1 and 2/3 through two-source permutes, then row 4
+const fn pi_index(y: usize, first: usize) -> [i64; 8] {
+    let mut idx = [0; 8];
+    let mut x = first;
+    while x < first + 2 && x < 5 {
+        idx[x] = (((x + 3 * y) % 5) + (x - first) * 8) as i64;
+        x += 1;
+    }
+    idx
+}
+
+#[inline(always)]
+unsafe fn setr(idx: [i64; 8]) -> __m512i {
+    _mm512_loadu_si512(idx.as_ptr().cast())
+}
+
+#[target_feature(enable = "avx512f")]
+pub(super) unsafe fn permute(lanes: &mut Lanes, rc: &[u64]) {
+    let mut rows = [_mm512_setzero_si512(); 5];
+    let mut rho = [_mm512_setzero_si512(); 5];
+    let mut pi = [[_mm512_setzero_si512(); 3]; 5];
+    for y in 0..5 {
+        rows[y] = _mm512_maskz_loadu_epi64(ROW, lanes[5 * y..].as_ptr().cast());
+        rho[y] = setr(core::array::from_fn(|x| if x < 5 { RHO[5 * y + x] as i64 } else { 0 }));
+        pi[y] = [setr(pi_index(y, 0)), setr(pi_index(y, 2)), setr(pi_index(y, 4))];
+    }
+    let prev = _mm512_setr_epi64(4, 0, 1, 2, 3, 0, 0, 0);
+    let next = _mm512_setr_epi64(1, 2, 3, 4, 0, 0, 0, 0);
+    let next2 = _mm512_setr_epi64(2, 3, 4, 0, 1, 0, 0, 0);
+    for &rc in rc {
+        // theta
+        let c = _mm512_ternarylogic_epi64::<XOR3>(
+            _mm512_ternarylogic_epi64::<XOR3>(rows[0], rows[1], rows[2]),
+            rows[3],
+            rows[4],
+        );
+        let d = _mm512_xor_si512(
+            _mm512_permutexvar_epi64(prev, c),
+            _mm512_rol_epi64::<1>(_mm512_permutexvar_epi64(next, c)),
+        );
+        // rho
+        let mut t = [_mm512_setzero_si512(); 5];
+        for y in 0..5 {
+            t[y] = _mm512_rolv_epi64(_mm512_xor_si512(rows[y], d), rho[y]);
+        }
+        // pi, then chi
+        for y in 0..5 {
+            let [i01, i23, i4] = pi[y];
+            let b = _mm512_mask_blend_epi64(
+                0b01100,
+                _mm512_permutex2var_epi64(t[0], i01, t[1]),
+                _mm512_permutex2var_epi64(t[2], i23, t[3]),
+            );
+            let b = _mm512_mask_permutexvar_epi64(b, 0b10000, i4, t[4]);
+            rows[y] = _mm512_ternarylogic_epi64::<CHI>(
+                b,
+                _mm512_permutexvar_epi64(next, b),
+                _mm512_permutexvar_epi64(next2, b),
+            );
+        }
+        // iota
+        rows[0] = _mm512_xor_si512(rows[0], _mm512_maskz_set1_epi64(1, rc as i64));
+    }
+    for y in 0..5 {
+        _mm512_mask_storeu_epi64(lanes[5 * y..].as_mut_ptr().cast(), ROW, rows[y]);
+    }
+}
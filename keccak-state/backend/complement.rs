@@ -0,0 +1,88 @@
+use super::{Lanes, RHO, PI};
+
+// lanes kept complemented between rounds (the "bebigokimisa" pattern)
+const M: Lanes = {
+    let mut m = [0; 25];
+    let pattern = [1, 2, 8, 12, 17, 20];
+    let mut i = 0;
+    while i < pattern.len() {
+        m[pattern[i]] = !0;
+        i += 1;
+    }
+    m
+};
+
+// theta, rho and pi are linear, so a complemented input leaves this constant pattern behind
+const N: Lanes = {
+    let mut c = [0u64; 5];
+    let mut x = 0;
+    while x < 5 {
+        c[x] = M[x] ^ M[x + 5] ^ M[x + 10] ^ M[x + 15] ^ M[x + 20];
+        x += 1;
+    }
+    let mut n = [0; 25];
+    let mut i = 0;
+    while i < 25 {
+        let x = i % 5;
+        let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+        n[PI[i]] = (M[i] ^ d).rotate_left(RHO[i]);
+        i += 1;
+    }
+    n
+};
+
+// b0 ^ (!b1 & b2), taking complemented inputs and producing a complemented output,
+// written so that constant masks turn into AND/OR forms instead of extra NOTs
+#[inline(always)]
+fn chi(b0: u64, b1: u64, b2: u64, i0: usize, i1: usize, i2: usize, m: u64) -> u64 {
+    let k = N[i0] ^ m;
+    match (N[i1] == 0, N[i2] == 0) {
+        (true, true) => b0 ^ (!b1 & b2) ^ k,
+        (false, true) => b0 ^ (b1 & b2) ^ k,
+        (true, false) => b0 ^ (b1 | b2) ^ !k,
+        (false, false) => b0 ^ (b1 & !b2) ^ k,
+    }
+}
+
+#[inline(always)]
+fn round(a: &mut Lanes, rc: u64) {
+    let c = [
+        a[0] ^ a[5] ^ a[10] ^ a[15] ^ a[20],
+        a[1] ^ a[6] ^ a[11] ^ a[16] ^ a[21],
+        a[2] ^ a[7] ^ a[12] ^ a[17] ^ a[22],
+        a[3] ^ a[8] ^ a[13] ^ a[18] ^ a[23],
+        a[4] ^ a[9] ^ a[14] ^ a[19] ^ a[24],
+    ];
+    let d = [
+        c[4] ^ c[1].rotate_left(1),
+        c[0] ^ c[2].rotate_left(1),
+        c[1] ^ c[3].rotate_left(1),
+        c[2] ^ c[4].rotate_left(1),
+        c[3] ^ c[0].rotate_left(1),
+    ];
+    let mut b = [0u64; 25];
+    for_each_lane!(I => {
+        b[PI[I]] = (a[I] ^ d[I % 5]).rotate_left(RHO[I]);
+    });
+    for_each_lane!(I => {
+        const I1: usize = (I + 1) % 5 + I / 5 * 5;
+        const I2: usize = (I + 2) % 5 + I / 5 * 5;
+        a[I] = chi(b[I], b[I1], b[I2], I, I1, I2, M[I]);
+    });
+    a[0] ^= rc;
+}
+
+pub(super) fn permute(lanes: &mut Lanes, rc: &[u64]) {
+    // a local copy lets the whole state live in registers
+    let mut a = *lanes;
+    for_each_lane!(I => {
+        a[I] ^= M[I];
+    });
+    for &rc in rc {
+        round(&mut a, rc);
+    }
+    for_each_lane!(I => {
+        a[I] ^= M[I];
+    });
+    *lanes = a;
+}
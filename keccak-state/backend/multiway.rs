@@ -0,0 +1,193 @@
+use super::{Lanes, RHO, PI};
+
+// lane i of every state next to each other, so W consecutive states load as one W-wide vector
+pub(crate) type LanesX<const N: usize> = [[u64; N]; 25];
+
+// without vectors every state is permuted on its own with a single-state backend
+pub(super) fn permute_each<const N: usize>(a: &mut LanesX<N>, permute: impl Fn(&mut Lanes)) {
+    for k in 0..N {
+        let mut lanes: Lanes = core::array::from_fn(|i| a[i][k]);
+        permute(&mut lanes);
+        for (a, lane) in a.iter_mut().zip(lanes) {
+            a[k] = lane;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod vector {
+    use core::arch::x86_64::*;
+    use super::{LanesX, RHO, PI};
+
+    // a vector holding the same lane of WIDTH states
+    pub(super) trait Vector: Copy {
+        const WIDTH: usize;
+        unsafe fn load(src: &[u64]) -> Self;
+        unsafe fn store(self, dst: &mut [u64]);
+        unsafe fn splat(v: u64) -> Self;
+        unsafe fn xor(self, b: Self) -> Self;
+        // !self & b
+        unsafe fn andnot(self, b: Self) -> Self;
+        // rotation by L, with R = 64 - L
+        unsafe fn rol<const L: i32, const R: i32>(self) -> Self;
+    }
+
+    impl Vector for __m128i {
+        const WIDTH: usize = 2;
+
+        #[inline(always)]
+        unsafe fn load(src: &[u64]) -> Self {
+            _mm_loadu_si128(src[..2].as_ptr().cast())
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, dst: &mut [u64]) {
+            _mm_storeu_si128(dst[..2].as_mut_ptr().cast(), self)
+        }
+
+        #[inline(always)]
+        unsafe fn splat(v: u64) -> Self {
+            _mm_set1_epi64x(v as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, b: Self) -> Self {
+            _mm_xor_si128(self, b)
+        }
+
+        #[inline(always)]
+        unsafe fn andnot(self, b: Self) -> Self {
+            _mm_andnot_si128(self, b)
+        }
+
+        #[inline(always)]
+        unsafe fn rol<const L: i32, const R: i32>(self) -> Self {
+            _mm_or_si128(_mm_slli_epi64::<L>(self), _mm_srli_epi64::<R>(self))
+        }
+    }
+
+    impl Vector for __m256i {
+        const WIDTH: usize = 4;
+
+        #[inline(always)]
+        unsafe fn load(src: &[u64]) -> Self {
+            _mm256_loadu_si256(src[..4].as_ptr().cast())
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, dst: &mut [u64]) {
+            _mm256_storeu_si256(dst[..4].as_mut_ptr().cast(), self)
+        }
+
+        #[inline(always)]
+        unsafe fn splat(v: u64) -> Self {
+            _mm256_set1_epi64x(v as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, b: Self) -> Self {
+            _mm256_xor_si256(self, b)
+        }
+
+        #[inline(always)]
+        unsafe fn andnot(self, b: Self) -> Self {
+            _mm256_andnot_si256(self, b)
+        }
+
+        #[inline(always)]
+        unsafe fn rol<const L: i32, const R: i32>(self) -> Self {
+            _mm256_or_si256(_mm256_slli_epi64::<L>(self), _mm256_srli_epi64::<R>(self))
+        }
+    }
+
+    impl Vector for __m512i {
+        const WIDTH: usize = 8;
+
+        #[inline(always)]
+        unsafe fn load(src: &[u64]) -> Self {
+            _mm512_loadu_si512(src[..8].as_ptr().cast())
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, dst: &mut [u64]) {
+            _mm512_storeu_si512(dst[..8].as_mut_ptr().cast(), self)
+        }
+
+        #[inline(always)]
+        unsafe fn splat(v: u64) -> Self {
+            _mm512_set1_epi64(v as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, b: Self) -> Self {
+            _mm512_xor_si512(self, b)
+        }
+
+        #[inline(always)]
+        unsafe fn andnot(self, b: Self) -> Self {
+            _mm512_andnot_si512(self, b)
+        }
+
+        #[inline(always)]
+        unsafe fn rol<const L: i32, const R: i32>(self) -> Self {
+            _mm512_rol_epi64::<L>(self)
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn permute_vector<V: Vector>(a: &mut [V; 25], rc: &[u64]) {
+        for &rc in rc {
+            // theta
+            let c: [V; 5] = core::array::from_fn(|x| a[x].xor(a[x + 5]).xor(a[x + 10]).xor(a[x + 15]).xor(a[x + 20]));
+            let d: [V; 5] = core::array::from_fn(|x| c[(x + 4) % 5].xor(c[(x + 1) % 5].rol::<1, 63>()));
+            // rho and pi
+            let mut b = [a[0]; 25];
+            for_each_lane!(I => {
+                const L: i32 = RHO[I] as i32;
+                b[PI[I]] = a[I].xor(d[I % 5]).rol::<L, { 64 - L }>();
+            });
+            // chi
+            for_each_lane!(I => {
+                const I1: usize = (I + 1) % 5 + I / 5 * 5;
+                const I2: usize = (I + 2) % 5 + I / 5 * 5;
+                a[I] = b[I].xor(b[I1].andnot(b[I2]));
+            });
+            // iota
+            a[0] = a[0].xor(V::splat(rc));
+        }
+    }
+
+    // N has to be a multiple of the vector width
+    #[inline(always)]
+    pub(super) unsafe fn permute<V: Vector, const N: usize>(lanes: &mut LanesX<N>, rc: &[u64]) {
+        for group in (0..N).step_by(V::WIDTH) {
+            let mut a: [V; 25] = core::array::from_fn(|i| V::load(&lanes[i][group..]));
+            permute_vector(&mut a, rc);
+            for (i, a) in a.into_iter().enumerate() {
+                a.store(&mut lanes[i][group..]);
+            }
+        }
+    }
+}
+
+// SSE2 is always there on x86_64
+#[cfg(target_arch = "x86_64")]
+pub(super) fn permute_sse2<const N: usize>(a: &mut LanesX<N>, rc: &[u64]) {
+    assert!(N.is_multiple_of(2));
+    // SAFETY: SSE2 is part of the x86_64 baseline
+    unsafe { vector::permute::<core::arch::x86_64::__m128i, N>(a, rc) }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn permute_avx2<const N: usize>(a: &mut LanesX<N>, rc: &[u64]) {
+    assert!(N.is_multiple_of(4));
+    vector::permute::<core::arch::x86_64::__m256i, N>(a, rc)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+pub(super) unsafe fn permute_avx512<const N: usize>(a: &mut LanesX<N>, rc: &[u64]) {
+    assert!(N.is_multiple_of(8));
+    vector::permute::<core::arch::x86_64::__m512i, N>(a, rc)
+}
@@ -0,0 +1,53 @@
+use crate::LANES;
+use super::{RHO, PI};
+
+const fn rotate_left(v: u64, n: u32, bits: u32) -> u64 {
+    let n = n % bits;
+    if n == 0 {
+        v
+    } else {
+        ((v << n) | (v >> (bits - n))) & mask(bits)
+    }
+}
+
+const fn mask(bits: u32) -> u64 {
+    u64::MAX >> (64 - bits)
+}
+
+// Keccak-p[25 * bits] on lanes zero-extended to u64, written with while loops so that the const
+// sponge can share it with the runtime backends
+#[inline(always)]
+pub(crate) const fn permute(a: &mut [u64; LANES], rc: &[u64], bits: u32) {
+    let mut round = 0;
+    while round < rc.len() {
+        // theta
+        let mut c = [0u64; 5];
+        let mut x = 0;
+        while x < 5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            x += 1;
+        }
+        let mut i = 0;
+        while i < LANES {
+            a[i] ^= c[(i + 4) % 5] ^ rotate_left(c[(i + 1) % 5], 1, bits);
+            i += 1;
+        }
+        // rho and pi
+        let mut b = [0u64; LANES];
+        let mut i = 0;
+        while i < LANES {
+            b[PI[i]] = rotate_left(a[i], RHO[i], bits);
+            i += 1;
+        }
+        // chi
+        let mut i = 0;
+        while i < LANES {
+            let (x, y) = (i % 5, i - i % 5);
+            a[i] = b[i] ^ (!b[(x + 1) % 5 + y] & b[(x + 2) % 5 + y]);
+            i += 1;
+        }
+        // iota
+        a[0] ^= rc[round] & mask(bits);
+        round += 1;
+    }
+}
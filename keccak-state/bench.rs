@@ -0,0 +1,57 @@
+// cargo bench -p keccak-state
+// single-state Keccak-f[1600] per backend, then N independent states against one N-way interleaved
+// state, each absorbing LEN bytes per state
+use std::{hint::black_box, time::{Duration, Instant}};
+use keccak_state::{Backend, KeccakState, KeccakStateX, KeccakF, Absorb, Squeeze, DSHAKE, R128};
+
+const LEN: usize = 16 << 20;
+const RUNS: usize = 5;
+const PERMUTATIONS: u32 = 200_000;
+
+fn best<F: FnMut()>(mut f: F) -> Duration {
+    (0..RUNS).map(|_| {
+        let start = Instant::now();
+        f();
+        start.elapsed()
+    }).min().unwrap()
+}
+
+fn bench_backends() {
+    for backend in Backend::ALL.into_iter().filter(|backend| backend.is_available()) {
+        let mut lanes = [0; 25];
+        let time = best(|| {
+            for _ in 0..PERMUTATIONS {
+                backend.permute(black_box(&mut lanes), 24);
+            }
+        });
+        println!("{:?}: {:.0} ns per permutation", backend, time.as_nanos() as f64 / PERMUTATIONS as f64);
+    }
+}
+
+fn bench<const N: usize>(inputs: &[Vec<u8>]) {
+    let single = best(|| {
+        for input in &inputs[..N] {
+            let mut state = KeccakState::<KeccakF, R128>::new(DSHAKE);
+            state.absorb(input);
+            let _ = black_box(state.squeeze_to_array::<32>());
+        }
+    });
+    let multi = best(|| {
+        let mut state = KeccakStateX::<KeccakF, R128, N>::new(DSHAKE);
+        state.absorb(core::array::from_fn(|k| inputs[k].as_slice()));
+        let _ = black_box(state.squeeze_to_arrays::<32>());
+    });
+    let mib = (N * LEN) as f64 / (1 << 20) as f64;
+    println!(
+        "x{}: {} separate {:.0} MiB/s, interleaved {:.0} MiB/s ({:.2}x)",
+        N, N, mib / single.as_secs_f64(), mib / multi.as_secs_f64(), single.as_secs_f64() / multi.as_secs_f64(),
+    );
+}
+
+fn main() {
+    bench_backends();
+    let inputs: Vec<Vec<u8>> = (0..8).map(|k| (0..LEN).map(|i| (i * 7 + k) as u8).collect()).collect();
+    bench::<2>(&inputs);
+    bench::<4>(&inputs);
+    bench::<8>(&inputs);
+}
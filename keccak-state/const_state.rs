@@ -0,0 +1,111 @@
+use crate::{KeccakState, KeccakF, backend::{RC, reference}, LANES, LANE_BYTES, BYTES, BITS, Absorbing, Squeezing};
+
+// Keccak-p[1600, rounds] usable in const contexts, the backends are much faster at runtime
+pub const fn keccak_p1600(lanes: &mut [u64; LANES], rounds: usize) {
+    assert!(rounds <= RC.len(), "Keccak-p[1600] has at most 24 rounds");
+    reference::permute(lanes, RC.split_at(RC.len() - rounds).1, 64)
+}
+
+#[inline(always)]
+pub const fn keccak_f1600(lanes: &mut [u64; LANES]) {
+    keccak_p1600(lanes, RC.len())
+}
+
+// byte-at-a-time Keccak-f[1600] sponge for const items, matches `KeccakState<KeccakF, R>`
+#[derive(Clone, Copy)]
+pub struct ConstKeccakState<const R: usize> {
+    lanes: [u64; LANES],
+    offset: usize,
+    delim: u8,
+    mode: bool,
+}
+
+impl<const R: usize> ConstKeccakState<R> {
+    pub const fn new(delim: u8) -> Self {
+        let () = KeccakState::<KeccakF, R>::CHECK_RATE;
+        ConstKeccakState { lanes: [0; LANES], offset: 0, delim, mode: Absorbing }
+    }
+
+    const fn xor_byte(&mut self, pos: usize, byte: u8) {
+        self.lanes[pos / LANE_BYTES] ^= (byte as u64) << (8 * (pos % LANE_BYTES));
+    }
+
+    const fn fill_block(&mut self) {
+        keccak_f1600(&mut self.lanes);
+        self.offset = 0;
+    }
+
+    const fn switch(&mut self, mode: bool) {
+        if self.mode != mode {
+            if mode == Squeezing {
+                self.xor_byte(self.offset, self.delim);
+                self.xor_byte(R - 1, 0x80);
+            }
+            self.fill_block();
+            self.mode = mode;
+        }
+    }
+
+    pub const fn absorb(&mut self, input: &[u8]) {
+        self.switch(Absorbing);
+        let mut i = 0;
+        while i < input.len() {
+            self.xor_byte(self.offset, input[i]);
+            self.offset += 1;
+            if self.offset == R {
+                self.fill_block();
+            }
+            i += 1;
+        }
+    }
+
+    pub const fn chain_absorb(mut self, input: &[u8]) -> Self {
+        self.absorb(input);
+        self
+    }
+
+    pub const fn absorb_zero(&mut self, len: usize) {
+        self.switch(Absorbing);
+        let mut i = 0;
+        while i < len {
+            self.offset += 1;
+            if self.offset == R {
+                self.fill_block();
+            }
+            i += 1;
+        }
+    }
+
+    pub const fn squeeze(&mut self, output: &mut [u8]) {
+        self.switch(Squeezing);
+        let mut i = 0;
+        while i < output.len() {
+            output[i] = (self.lanes[self.offset / LANE_BYTES] >> (8 * (self.offset % LANE_BYTES))) as u8;
+            self.offset += 1;
+            if self.offset == R {
+                self.fill_block();
+            }
+            i += 1;
+        }
+    }
+
+    pub const fn squeeze_to_array<const N: usize>(&mut self) -> [u8; N] {
+        let mut buf = [0; N];
+        self.squeeze(&mut buf);
+        buf
+    }
+
+    // same as `KeccakState::to_initial`
+    pub const fn to_initial(&self) -> Option<[u8; BYTES(BITS)]> {
+        if self.offset != 0 || self.mode != Absorbing {
+            return None;
+        }
+        let mut bytes = [0; BYTES(BITS)];
+        let mut i = 0;
+        while i < BYTES(BITS) {
+            bytes[i] = (self.lanes[i / LANE_BYTES] >> (8 * (i % LANE_BYTES))) as u8;
+            i += 1;
+        }
+        Some(bytes)
+    }
+}
@@ -0,0 +1,129 @@
+use digest::{
+    Update, FixedOutput, FixedOutputReset, ExtendableOutput, ExtendableOutputReset, XofReader, HashMarker, Output, OutputSizeUser,
+    crypto_common::BlockSizeUser,
+    consts::{U28, U32, U48, U64, U72, U104, U136, U144, U168},
+};
+use crate::{
+    Absorb, Squeeze, Reset,
+    FixedHash, Shake,
+    DSHA3, DKeccak, R128, R224, R256, R384, R512,
+};
+
+// output stage returned by `ExtendableOutput::finalize_xof`
+#[derive(Clone)]
+pub struct DigestReader<T: Squeeze>(pub(crate) T);
+
+impl<T: Squeeze> DigestReader<T> {
+    #[inline(always)]
+    pub fn new(inner: T) -> Self {
+        DigestReader(inner)
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Squeeze> XofReader for DigestReader<T> {
+    #[inline(always)]
+    fn read(&mut self, buffer: &mut [u8]) {
+        self.0.squeeze(buffer);
+    }
+}
+
+impl<const D: u8, const R: usize, const N: usize> Update for FixedHash<D, R, N> {
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.absorb(data);
+    }
+}
+
+impl<const D: u8, const R: usize, const N: usize> digest::Reset for FixedHash<D, R, N> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+}
+
+impl<const D: u8, const R: usize, const N: usize> HashMarker for FixedHash<D, R, N> {}
+
+// typenum sizes cannot be derived from const generics, so only the named instances are covered
+macro_rules! impl_fixed_output {
+    ($($d:ident $r:ident $n:literal => $rate:ident $size:ident)*) => {$(
+        impl BlockSizeUser for FixedHash<$d, $r, $n> {
+            type BlockSize = $rate;
+        }
+
+        impl OutputSizeUser for FixedHash<$d, $r, $n> {
+            type OutputSize = $size;
+        }
+
+        impl FixedOutput for FixedHash<$d, $r, $n> {
+            #[inline]
+            fn finalize_into(self, out: &mut Output<Self>) {
+                out.copy_from_slice(&self.finalize());
+            }
+        }
+
+        impl FixedOutputReset for FixedHash<$d, $r, $n> {
+            #[inline]
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                out.copy_from_slice(&self.clone().finalize());
+                Reset::reset(self);
+            }
+        }
+    )*};
+}
+
+impl_fixed_output! {
+    DSHA3 R224 28 => U144 U28
+    DSHA3 R256 32 => U136 U32
+    DSHA3 R384 48 => U104 U48
+    DSHA3 R512 64 => U72 U64
+    DKeccak R224 28 => U144 U28
+    DKeccak R256 32 => U136 U32
+    DKeccak R384 48 => U104 U48
+    DKeccak R512 64 => U72 U64
+}
+
+impl<const R: usize> Update for Shake<R> {
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.absorb(data);
+    }
+}
+
+impl<const R: usize> ExtendableOutput for Shake<R> {
+    type Reader = DigestReader<Shake<R>>;
+
+    #[inline(always)]
+    fn finalize_xof(self) -> Self::Reader {
+        DigestReader(self)
+    }
+}
+
+impl<const R: usize> ExtendableOutputReset for Shake<R> {
+    fn finalize_xof_reset(&mut self) -> Self::Reader {
+        let reader = DigestReader(self.clone());
+        Reset::reset(self);
+        reader
+    }
+}
+
+impl<const R: usize> digest::Reset for Shake<R> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+}
+
+impl<const R: usize> HashMarker for Shake<R> {}
+
+impl BlockSizeUser for Shake<R128> {
+    type BlockSize = U168;
+}
+
+impl BlockSizeUser for Shake<R256> {
+    type BlockSize = U136;
+}
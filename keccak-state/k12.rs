@@ -0,0 +1,136 @@
+use core::mem::MaybeUninit;
+use crate::{KeccakState, KeccakP, KeccakP14, Permutation, Absorb, Squeeze, SqueezeXor, SqueezeSkip, R128, R256};
+
+const CHUNK: usize = 8192;
+
+const DSingle : u8 = 0x07;
+const DLeaf   : u8 = 0x0B;
+const DFinal  : u8 = 0x06;
+
+// big-endian x without leading zeros, followed by its byte count
+fn absorb_length_encode<T: Absorb>(ctx: &mut T, x: usize) {
+    let lz = (x.leading_zeros() / 8) as usize;
+    let x = x.to_be_bytes();
+    ctx.absorb(&x[lz..]);
+    ctx.absorb(&[(x.len() - lz) as u8]);
+}
+
+#[derive(Clone)]
+pub struct Kangaroo<P: Permutation, const R: usize, const CV: usize> {
+    node: KeccakState<P, R>,
+    leaf: KeccakState<P, R>,
+    chunk_len: usize,
+    // completed chunks including the first one
+    chunks: usize,
+}
+
+impl<P: Permutation, const R: usize, const CV: usize> Kangaroo<P, R, CV> {
+    pub fn new() -> Self {
+        Kangaroo {
+            node: KeccakState::new(DSingle),
+            leaf: KeccakState::new(DLeaf),
+            chunk_len: 0,
+            chunks: 0,
+        }
+    }
+
+    // called only when more input follows a full chunk
+    fn next_chunk(&mut self) {
+        if self.chunks == 0 {
+            self.node.absorb(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+        } else {
+            let cv: [u8; CV] = self.leaf.squeeze_to_array();
+            self.node.absorb(&cv);
+            self.leaf = KeccakState::new(DLeaf);
+        }
+        self.chunks += 1;
+        self.chunk_len = 0;
+    }
+
+    pub fn finalize_custom(mut self, custom_string: &[u8]) -> KangarooXof<P, R> {
+        self.absorb(custom_string);
+        absorb_length_encode(&mut self, custom_string.len());
+        let Kangaroo { mut node, mut leaf, chunks, .. } = self;
+        if chunks == 0 {
+            return KangarooXof { ctx: node };
+        }
+        let cv: [u8; CV] = leaf.squeeze_to_array();
+        node.absorb(&cv);
+        absorb_length_encode(&mut node, chunks);
+        node.absorb(&[0xFF, 0xFF]);
+        KangarooXof { ctx: node.change_delim(DFinal) }
+    }
+
+    #[inline]
+    pub fn finalize(self) -> KangarooXof<P, R> {
+        self.finalize_custom(&[])
+    }
+
+    #[inline]
+    pub fn once(input: &[u8], custom_string: &[u8], output: &mut [u8]) {
+        Self::new().chain_absorb(input).finalize_custom(custom_string).squeeze(output)
+    }
+
+    #[inline]
+    pub fn once_to_array<const N: usize>(input: &[u8], custom_string: &[u8]) -> [u8; N] {
+        Self::new().chain_absorb(input).finalize_custom(custom_string).squeeze_to_array()
+    }
+}
+
+impl<P: Permutation, const R: usize, const CV: usize> Default for Kangaroo<P, R, CV> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Permutation, const R: usize, const CV: usize> Absorb for Kangaroo<P, R, CV> {
+    fn absorb(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            if self.chunk_len == CHUNK {
+                self.next_chunk();
+            }
+            let len = (CHUNK - self.chunk_len).min(input.len());
+            let (part, rest) = input.split_at(len);
+            if self.chunks == 0 {
+                self.node.absorb(part);
+            } else {
+                self.leaf.absorb(part);
+            }
+            self.chunk_len += len;
+            input = rest;
+        }
+    }
+}
+
+pub struct KangarooXof<P: Permutation, const R: usize> {
+    ctx: KeccakState<P, R>,
+}
+
+impl<P: Permutation, const R: usize> Squeeze for KangarooXof<P, R> {
+    #[inline(always)]
+    fn squeeze(&mut self, output: &mut [u8]) {
+        self.ctx.squeeze(output);
+    }
+
+    #[inline(always)]
+    fn squeeze_uninit<'b>(&mut self, output: &'b mut [MaybeUninit<u8>]) -> &'b mut [u8] {
+        self.ctx.squeeze_uninit(output)
+    }
+}
+
+impl<P: Permutation, const R: usize> SqueezeXor for KangarooXof<P, R> {
+    #[inline(always)]
+    fn squeeze_xor(&mut self, output: &mut [u8]) {
+        self.ctx.squeeze_xor(output);
+    }
+}
+
+impl<P: Permutation, const R: usize> SqueezeSkip for KangarooXof<P, R> {
+    #[inline(always)]
+    fn squeeze_skip(&mut self, len: usize) {
+        self.ctx.squeeze_skip(len);
+    }
+}
+
+pub type KangarooTwelve = Kangaroo<KeccakP, R128, 32>;
+pub type MarsupilamiFourteen = Kangaroo<KeccakP14, R256, 64>;
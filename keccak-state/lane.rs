@@ -0,0 +1,79 @@
+use core::ops::{BitAnd, BitXor, BitXorAssign, Not};
+#[cfg(feature = "zeroize-on-drop")] use zeroize::Zeroize;
+use crate::{backend, LANES};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+// one of the 25 lanes of a Keccak-p state, the state width is 25 times its bits
+pub trait Lane: Copy + Default + BitXor<Output = Self> + BitXorAssign + BitAnd<Output = Self> + Not<Output = Self> + sealed::Sealed {
+    const BITS: u32;
+    const BYTES: usize;
+    // rounds of Keccak-f at this width, 12 + 2l
+    const ROUNDS: usize;
+
+    // keeps the low bits, which is how round constants shrink with the lane
+    fn truncate(v: u64) -> Self;
+
+    fn widen(self) -> u64;
+
+    fn from_le(bytes: &[u8]) -> Self;
+
+    fn write_le(self, bytes: &mut [u8]);
+
+    fn permute(lanes: &mut [Self; LANES], rounds: usize);
+
+    #[cfg(feature = "zeroize-on-drop")]
+    fn zeroize(lanes: &mut [Self; LANES]);
+}
+
+macro_rules! impl_lane {
+    ($($ty:ty => $rounds:literal, $permute:path;)*) => {$(
+        impl sealed::Sealed for $ty {}
+
+        impl Lane for $ty {
+            const BITS: u32 = <$ty>::BITS;
+            const BYTES: usize = core::mem::size_of::<$ty>();
+            const ROUNDS: usize = $rounds;
+
+            #[inline(always)]
+            fn truncate(v: u64) -> Self {
+                v as $ty
+            }
+
+            #[inline(always)]
+            fn widen(self) -> u64 {
+                self as u64
+            }
+
+            #[inline(always)]
+            fn from_le(bytes: &[u8]) -> Self {
+                <$ty>::from_le_bytes(bytes.try_into().unwrap())
+            }
+
+            #[inline(always)]
+            fn write_le(self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&self.to_le_bytes())
+            }
+
+            #[inline(always)]
+            fn permute(lanes: &mut [Self; LANES], rounds: usize) {
+                $permute(lanes, rounds)
+            }
+
+            #[cfg(feature = "zeroize-on-drop")]
+            #[inline(always)]
+            fn zeroize(lanes: &mut [Self; LANES]) {
+                Zeroize::zeroize(lanes)
+            }
+        }
+    )*};
+}
+
+impl_lane! {
+    u8 => 18, backend::permute_portable;
+    u16 => 20, backend::permute_portable;
+    u32 => 22, backend::permute_portable;
+    u64 => 24, backend::permute;
+}
@@ -5,6 +5,7 @@
 
 #[cfg(feature = "alloc")] extern crate alloc;
 #[cfg(feature = "zeroize-on-drop")] use zeroize::Zeroize;
+use core::{marker::PhantomData, mem::MaybeUninit};
 
 // region: consts
 
@@ -18,10 +19,39 @@ pub const fn BYTES(bits: usize) -> usize {
     bits / 8
 }
 
-pub const KeccakF: bool = true;
-pub const KeccakP: bool = false;
+pub trait Permutation {
+    type Lane: Lane;
+    const ROUNDS: usize;
+}
+
+// Keccak-p[25 * L::BITS, NR], the last NR rounds of Keccak-f at that width
+#[derive(Clone, Copy)]
+pub struct KeccakRounds<const NR: usize, L: Lane = u64>(PhantomData<L>);
+
+impl<const NR: usize, L: Lane> Permutation for KeccakRounds<NR, L> {
+    type Lane = L;
+    const ROUNDS: usize = {
+        assert!(NR >= 1 && NR <= L::ROUNDS, "round count must be in 1..=12 + 2l");
+        NR
+    };
+}
+
+pub type KeccakF = KeccakRounds<24>;
+
+pub type KeccakP = KeccakRounds<12>;
+
+// MarsupilamiFourteen
+pub type KeccakP14 = KeccakRounds<14>;
+
+pub type KeccakF800 = KeccakRounds<22, u32>;
+
+pub type KeccakF400 = KeccakRounds<20, u16>;
+
+pub type KeccakF200 = KeccakRounds<18, u8>;
 
 pub const fn R(bits: usize) -> usize {
+    assert!(bits != 0 && bits < 800, "security level must be in 1..800 bits");
+    assert!(bits.is_multiple_of(32), "security level must give a whole number of lanes");
     200 - bits / 4
 }
 
@@ -33,6 +63,38 @@ pub const R384: usize = R(384);
 pub const R512: usize = R(512);
 pub const R544: usize = R(544);
 
+// security level in bits, the capacity is twice that
+#[derive(Clone, Copy)]
+pub struct Security<const LEVEL: usize>;
+
+impl<const LEVEL: usize> Security<LEVEL> {
+    pub const CAPACITY: usize = BYTES(2 * LEVEL);
+    pub const RATE: usize = R(LEVEL);
+
+    // rate at a narrower width, e.g. `Sec128::rate::<u32>()` for Keccak-f[800]
+    pub const fn rate<L: Lane>() -> usize {
+        NarrowRate::<L, LEVEL>::RATE
+    }
+}
+
+struct NarrowRate<L, const LEVEL: usize>(PhantomData<L>);
+
+impl<L: Lane, const LEVEL: usize> NarrowRate<L, LEVEL> {
+    // a capacity that does not fit the narrower state fails to compile
+    const RATE: usize = {
+        let capacity = Security::<LEVEL>::CAPACITY;
+        assert!(capacity != 0 && capacity < LANES * L::BYTES, "security level is out of range for this width");
+        assert!(capacity.is_multiple_of(L::BYTES), "security level must give a whole number of lanes");
+        LANES * L::BYTES - capacity
+    };
+}
+
+pub type Sec128 = Security<128>;
+pub type Sec224 = Security<224>;
+pub type Sec256 = Security<256>;
+pub type Sec384 = Security<384>;
+pub type Sec512 = Security<512>;
+
 pub const DKeccak : u8 = 0x01;
 pub const DSHA3   : u8 = 0x06;
 pub const DSHAKE  : u8 = 0x1f;
@@ -108,59 +170,134 @@ impl IOBuf for Skip {
     fn exec(&mut self, _buf_part: &mut [u8], _iobuf_offset: usize, _len: usize) { }
 }
 
+mod out_uninit;
+pub use out_uninit::OutUninit;
+
+// endregion
+
+// region: lanes
+
+const LANES: usize = 25;
+
+const LANE_BYTES: usize = BYTES(64);
+
+mod lane;
+pub use lane::Lane;
+
+#[inline(always)]
+fn lanes_from_bytes(bytes: &[u8; BYTES(BITS)]) -> [u64; WORDS(BITS)] {
+    core::array::from_fn(|i| u64::from_le_bytes(bytes[i * LANE_BYTES..][..LANE_BYTES].try_into().unwrap()))
+}
+
+#[inline(always)]
+fn lanes_to_bytes(lanes: &[u64; WORDS(BITS)]) -> [u8; BYTES(BITS)] {
+    let mut bytes = [0; BYTES(BITS)];
+    for (chunk, lane) in bytes.chunks_exact_mut(LANE_BYTES).zip(lanes) {
+        chunk.copy_from_slice(&lane.to_le_bytes());
+    }
+    bytes
+}
+
+#[inline(always)]
+fn xor_byte<L: Lane>(lanes: &mut [L; LANES], pos: usize, byte: u8) {
+    lanes[pos / L::BYTES] ^= L::truncate((byte as u64) << (8 * (pos % L::BYTES)));
+}
+
+// little-endian byte view of the lanes covering `start..start + len`, written back afterwards
+#[inline(always)]
+fn with_bytes<L: Lane, F: FnOnce(&mut [u8])>(lanes: &mut [L; LANES], start: usize, len: usize, f: F) {
+    let lanes_range = (start / L::BYTES)..(start + len).div_ceil(L::BYTES);
+    let mut bytes = [0; BYTES(BITS)];
+    for i in lanes_range.clone() {
+        lanes[i].write_le(&mut bytes[i * L::BYTES..][..L::BYTES]);
+    }
+    f(&mut bytes[start..][..len]);
+    for i in lanes_range {
+        lanes[i] = L::from_le(&bytes[i * L::BYTES..][..L::BYTES]);
+    }
+    #[cfg(feature = "zeroize-on-drop")]
+    bytes.zeroize();
+}
+
+mod backend;
+pub use backend::Backend;
+
 // endregion
 
 // region: state
 
 #[derive(Clone)]
-pub struct KeccakState<const P: bool, const R: usize> {
-    buf: [u8; BYTES(BITS)],
+pub struct KeccakState<P: Permutation, const R: usize> {
+    lanes: [P::Lane; LANES],
     offset: usize,
     delim: u8,
     mode: bool,
+    permutation: PhantomData<P>,
 }
 
 #[cfg(feature = "zeroize-on-drop")]
-impl<const P: bool, const R: usize> Drop for KeccakState<P, R> {
+impl<P: Permutation, const R: usize> Drop for KeccakState<P, R> {
     fn drop(&mut self) {
-        self.buf.zeroize();
+        P::Lane::zeroize(&mut self.lanes);
         self.offset = 0;
     }
 }
 
-impl<const P: bool, const R: usize> KeccakState<P, R> {
-    pub fn with_initial(delim: u8, buf: [u8; BYTES(BITS)]) -> Self {
-        // TODO complie time
+impl<P: Permutation, const R: usize> KeccakState<P, R> {
+    pub const STATE_BYTES: usize = LANES * P::Lane::BYTES;
+
+    // evaluated once per monomorphization, so an invalid R or round count fails to compile, but only
+    // where `new` is instantiated and code is generated: a type alias such as `KeccakState<KeccakF, 0>`
+    // alone is accepted, and `cargo check` skips the evaluation entirely, only `cargo build` reports it
+    pub(crate) const CHECK_RATE: () = {
+        let _ = P::ROUNDS;
         assert!(R != 0, "rate cannot be equal 0");
+        assert!(R < Self::STATE_BYTES, "rate must be less than the state size");
+        assert!(R.is_multiple_of(P::Lane::BYTES), "rate must be a whole number of lanes");
+    };
+
+    pub fn new(delim: u8) -> Self {
+        let () = Self::CHECK_RATE;
         KeccakState {
-            buf,
+            lanes: [P::Lane::default(); LANES],
             offset: 0,
             delim,
             mode: Absorbing,
+            permutation: PhantomData,
         }
     }
 
-    pub fn new(delim: u8) -> Self {
-        Self::with_initial(delim, [0; BYTES(BITS)])
+    #[inline(always)]
+    pub fn delim(&self) -> u8 {
+        self.delim
+    }
+
+    fn pad(&mut self) {
+        xor_byte(&mut self.lanes, self.offset, self.delim);
+        xor_byte(&mut self.lanes, R - 1, 0x80);
+    }
+
+    pub fn change_delim(self, delim: u8) -> Self {
+        let KeccakState { lanes, offset, mode, delim: _, permutation } = self;
+        KeccakState { lanes, offset, mode, delim, permutation }
+    }
+}
+
+// the raw state bytes are only exposed for Keccak-p[1600]
+impl<P: Permutation<Lane = u64>, const R: usize> KeccakState<P, R> {
+    pub fn with_initial(delim: u8, buf: [u8; BYTES(BITS)]) -> Self {
+        let mut state = Self::new(delim);
+        state.lanes = lanes_from_bytes(&buf);
+        state
     }
 
     pub fn to_initial(self) -> Option<[u8; BYTES(BITS)]> {
         if self.offset == 0 && matches!(self.mode, Absorbing) {
-            Some(self.buf)
+            Some(lanes_to_bytes(&self.lanes))
         } else {
             None
         }
     }
-
-    fn pad(&mut self) {
-        self.buf[self.offset] ^= self.delim;
-        self.buf[R - 1] ^= 0x80;
-    }
-
-    pub fn change_delim(self, delim: u8) -> Self {
-        let KeccakState { buf, offset, mode, delim: _ } = self;
-        KeccakState { buf, offset, mode, delim }
-    }
 }
 
 // endregion
@@ -175,45 +312,29 @@ pub trait Switch: Foldable {
     fn switch<const M: bool>(&mut self);
 }
 
-impl<const P: bool, const R: usize> Foldable for KeccakState<P, R> {
+impl<P: Permutation, const R: usize> Foldable for KeccakState<P, R> {
     fn fold<B: IOBuf>(&mut self, iobuf: &mut B) {
         let mut iobuf_offset = 0;
         let mut iobuf_rest = iobuf.len();
         let mut len = R - self.offset;
         while iobuf_rest >= len {
-            iobuf.exec(&mut self.buf[self.offset..], iobuf_offset, len);
+            with_bytes(&mut self.lanes, self.offset, len, |buf_part| iobuf.exec(buf_part, iobuf_offset, len));
             self.fill_block();
             iobuf_offset += len;
             iobuf_rest -= len;
             len = R;
         }
-        iobuf.exec(&mut self.buf[self.offset..], iobuf_offset, iobuf_rest);
+        with_bytes(&mut self.lanes, self.offset, iobuf_rest, |buf_part| iobuf.exec(buf_part, iobuf_offset, iobuf_rest));
         self.offset += iobuf_rest;
     }
 
     fn fill_block(&mut self) {
-        let words: &mut [u64; WORDS(BITS)] = unsafe { core::mem::transmute(&mut self.buf) };
-        #[cfg(target_endian = "big")]
-        #[inline]
-        fn swap_endianess(words: &mut [u64; WORDS(BITS)]) {
-            for item in words {
-                *item = item.swap_bytes();
-            }
-        }
-        #[cfg(target_endian = "big")]
-        swap_endianess(words);
-        if P == KeccakF {
-            keccak::f1600(words);
-        } else {
-            keccak::p1600(words, 12);
-        }
-        #[cfg(target_endian = "big")]
-        swap_endianess(words);
+        P::Lane::permute(&mut self.lanes, P::ROUNDS);
         self.offset = 0;
     }
 }
 
-impl<const P: bool, const R: usize> Switch for KeccakState<P, R> {
+impl<P: Permutation, const R: usize> Switch for KeccakState<P, R> {
     #[inline]
     fn switch<const M: bool>(&mut self) {
         if self.mode != M {
@@ -253,12 +374,23 @@ pub trait Squeeze {
         buf
     }
 
+    #[inline]
+    fn squeeze_uninit<'b>(&mut self, output: &'b mut [MaybeUninit<u8>]) -> &'b mut [u8] {
+        let output = out_uninit::zeroed(output);
+        self.squeeze(output);
+        output
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn squeeze_to_box(&mut self, len: usize) -> alloc::boxed::Box<[u8]> {
+        out_uninit::squeeze_to_box(self, len)
+    }
+
     #[cfg(feature = "alloc")]
     #[inline]
     fn squeeze_to_vec(&mut self, len: usize) -> alloc::vec::Vec<u8> {
-        let mut buf = alloc::vec::from_elem(0, len);
-        self.squeeze(&mut buf);
-        buf
+        self.squeeze_to_box(len).into_vec()
     }
 }
 
@@ -276,20 +408,10 @@ pub trait Reset {
     fn reset(&mut self);
 }
 
-// TODO merge to Absorb after Foldable complete
 #[cfg(feature = "seed")]
-pub trait AbsorbSeed: Absorb {
-    fn absorb_seed<const N: usize>(&mut self) {
-        let mut buf: [u8; N] = [0; N];
-        getrandom::fill(&mut buf).unwrap();
-        self.absorb(&buf);
-        #[cfg(feature = "zeroize-on-drop")]
-        buf.zeroize();
-    }
-}
-
+mod seed;
 #[cfg(feature = "seed")]
-impl<T: Absorb> AbsorbSeed for T {}
+pub use seed::AbsorbSeed;
 
 // endregion
 
@@ -314,6 +436,13 @@ impl<T: Foldable + Switch> Squeeze for T {
         self.switch::<Squeezing>();
         self.fold(&mut Out::<COPY>(output));
     }
+
+    fn squeeze_uninit<'b>(&mut self, output: &'b mut [MaybeUninit<u8>]) -> &'b mut [u8] {
+        self.switch::<Squeezing>();
+        let mut output = OutUninit::new(output);
+        self.fold(&mut output);
+        output.finish()
+    }
 }
 
 impl<T: Foldable + Switch> SqueezeXor for T {
@@ -330,15 +459,51 @@ impl<T: Foldable + Switch> SqueezeSkip for T {
     }
 }
 
-impl<const P: bool, const R: usize> Reset for KeccakState<P, R> {
+impl<P: Permutation, const R: usize> Reset for KeccakState<P, R> {
     fn reset(&mut self) {
         #[cfg(feature = "zeroize-on-drop")]
-        self.buf.zeroize();
+        P::Lane::zeroize(&mut self.lanes);
         #[cfg(not(feature = "zeroize-on-drop"))]
-        let _ = core::mem::replace(&mut self.buf, [0; BYTES(BITS)]);
+        let _ = core::mem::replace(&mut self.lanes, [P::Lane::default(); LANES]);
         self.offset = 0;
         self.mode = Absorbing;
     }
 }
 
 // endregion
+
+mod const_state;
+pub use const_state::{ConstKeccakState, keccak_p1600, keccak_f1600};
+
+mod multi;
+pub use multi::{KeccakStateX, KeccakStateX2, KeccakStateX4, KeccakStateX8};
+
+mod snapshot;
+pub use snapshot::{SNAPSHOT_MAGIC, SNAPSHOT_VERSION, SNAPSHOT_LEN};
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+pub use serde_impl::SerdeBytes;
+
+mod sha3;
+pub use sha3::{
+    FixedHash,
+    Sha3, Sha3_224, Sha3_256, Sha3_384, Sha3_512,
+    Keccak, Keccak224, Keccak256, Keccak384, Keccak512,
+    Shake, Shake128, Shake256,
+};
+
+mod turboshake;
+pub use turboshake::{TurboShake, TurboShake128, TurboShake256, DTurboSHAKE};
+
+mod k12;
+pub use k12::{Kangaroo, KangarooXof, KangarooTwelve, MarsupilamiFourteen};
+
+#[cfg(feature = "digest")]
+mod digest_impl;
+#[cfg(feature = "digest")]
+pub use digest_impl::DigestReader;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,136 @@
+use core::marker::PhantomData;
+#[cfg(feature = "zeroize-on-drop")] use zeroize::Zeroize;
+use crate::{KeccakState, Permutation, backend::{self, LanesX}, LANE_BYTES, BYTES, BITS, Absorbing, Squeezing};
+
+// N sponges that share rate, delimiter and position, permuted together
+#[derive(Clone)]
+pub struct KeccakStateX<P: Permutation<Lane = u64>, const R: usize, const N: usize> {
+    lanes: LanesX<N>,
+    offset: usize,
+    delim: u8,
+    mode: bool,
+    permutation: PhantomData<P>,
+}
+
+pub type KeccakStateX2<P, const R: usize> = KeccakStateX<P, R, 2>;
+pub type KeccakStateX4<P, const R: usize> = KeccakStateX<P, R, 4>;
+pub type KeccakStateX8<P, const R: usize> = KeccakStateX<P, R, 8>;
+
+#[cfg(feature = "zeroize-on-drop")]
+impl<P: Permutation<Lane = u64>, const R: usize, const N: usize> Drop for KeccakStateX<P, R, N> {
+    fn drop(&mut self) {
+        self.lanes.zeroize();
+        self.offset = 0;
+    }
+}
+
+impl<P: Permutation<Lane = u64>, const R: usize, const N: usize> KeccakStateX<P, R, N> {
+    pub fn new(delim: u8) -> Self {
+        let () = KeccakState::<P, R>::CHECK_RATE;
+        KeccakStateX {
+            lanes: [[0; N]; 25],
+            offset: 0,
+            delim,
+            mode: Absorbing,
+            permutation: PhantomData,
+        }
+    }
+
+    // None unless all states are at the same position with the same delimiter
+    pub fn from_states(states: [KeccakState<P, R>; N]) -> Option<Self> {
+        let first = states.first()?;
+        let (offset, delim, mode) = (first.offset, first.delim, first.mode);
+        if !states.iter().all(|s| s.offset == offset && s.delim == delim && s.mode == mode) {
+            return None;
+        }
+        let mut lanes = [[0; N]; 25];
+        for (k, state) in states.iter().enumerate() {
+            for (i, lane) in state.lanes.iter().enumerate() {
+                lanes[i][k] = *lane;
+            }
+        }
+        Some(KeccakStateX { lanes, offset, delim, mode, permutation: PhantomData })
+    }
+
+    pub fn into_states(self) -> [KeccakState<P, R>; N] {
+        core::array::from_fn(|k| {
+            let mut state = KeccakState::with_initial(self.delim, [0; BYTES(BITS)]);
+            state.lanes = core::array::from_fn(|i| self.lanes[i][k]);
+            state.offset = self.offset;
+            state.mode = self.mode;
+            state
+        })
+    }
+
+    fn permute(&mut self) {
+        backend::permute_x(&mut self.lanes, P::ROUNDS);
+        self.offset = 0;
+    }
+
+    fn switch(&mut self, mode: bool) {
+        if self.mode != mode {
+            if mode == Squeezing {
+                let (delim, end) = ((self.delim as u64) << (8 * (self.offset % LANE_BYTES)), 0x80 << (8 * ((R - 1) % LANE_BYTES)));
+                for k in 0..N {
+                    self.lanes[self.offset / LANE_BYTES][k] ^= delim;
+                    self.lanes[(R - 1) / LANE_BYTES][k] ^= end;
+                }
+            }
+            self.permute();
+            self.mode = mode;
+        }
+    }
+
+    // runs f(lane, begin, io offset, len) over `len` bytes, at most one lane at a time, permuting at block ends.
+    // the rate is a whole number of lanes, so only the first and last lane can be partial
+    #[inline(always)]
+    fn fold<F: FnMut(&mut [u64; N], usize, usize, usize)>(&mut self, len: usize, mut f: F) {
+        let mut io_offset = 0;
+        while io_offset < len {
+            let begin = self.offset % LANE_BYTES;
+            let part = (LANE_BYTES - begin).min(len - io_offset);
+            f(&mut self.lanes[self.offset / LANE_BYTES], begin, io_offset, part);
+            self.offset += part;
+            io_offset += part;
+            if self.offset == R {
+                self.permute();
+            }
+        }
+    }
+
+    pub fn absorb(&mut self, inputs: [&[u8]; N]) {
+        let len = inputs.first().map_or(0, |i| i.len());
+        assert!(inputs.iter().all(|i| i.len() == len), "all inputs must have the same length");
+        self.switch(Absorbing);
+        self.fold(len, |lane, begin, i, part| {
+            if part == LANE_BYTES {
+                for (lane, input) in lane.iter_mut().zip(&inputs) {
+                    *lane ^= u64::from_le_bytes(input[i..][..LANE_BYTES].try_into().unwrap());
+                }
+            } else {
+                for (lane, input) in lane.iter_mut().zip(&inputs) {
+                    let mut bytes = [0; LANE_BYTES];
+                    bytes[begin..][..part].copy_from_slice(&input[i..][..part]);
+                    *lane ^= u64::from_le_bytes(bytes);
+                }
+            }
+        });
+    }
+
+    pub fn squeeze(&mut self, mut outputs: [&mut [u8]; N]) {
+        let len = outputs.first().map_or(0, |o| o.len());
+        assert!(outputs.iter().all(|o| o.len() == len), "all outputs must have the same length");
+        self.switch(Squeezing);
+        self.fold(len, |lane, begin, i, part| {
+            for (lane, output) in lane.iter().zip(&mut outputs) {
+                output[i..][..part].copy_from_slice(&lane.to_le_bytes()[begin..][..part]);
+            }
+        });
+    }
+
+    pub fn squeeze_to_arrays<const L: usize>(&mut self) -> [[u8; L]; N] {
+        let mut bufs = [[0; L]; N];
+        self.squeeze(bufs.each_mut().map(|b| b.as_mut_slice()));
+        bufs
+    }
+}
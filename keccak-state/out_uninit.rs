@@ -1,112 +1,58 @@
-use crate::{IOBuf, Foldable, Switch, Squeezing};
-use core::{mem::MaybeUninit, ptr::copy_nonoverlapping};
-#[cfg(feature = "alloc")] use core::{alloc::Layout, ptr::NonNull};
-#[cfg(feature = "alloc")] use alloc::{alloc::{alloc, handle_alloc_error}, boxed::Box};
+use crate::IOBuf;
+use core::mem::MaybeUninit;
 
-struct OutUninitStack<const N: usize> {
-    data: [MaybeUninit<u8>; N],
+pub struct OutUninit<'b> {
+    buf: &'b mut [MaybeUninit<u8>],
+    written: usize,
 }
 
-impl<const N: usize> OutUninitStack<N> {
-    fn new() -> Self {
-        // use MaybeUninit::uninit_array() when stable
-        let data: [MaybeUninit<u8>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-        Self { data }
+impl<'b> OutUninit<'b> {
+    #[inline(always)]
+    pub fn new(buf: &'b mut [MaybeUninit<u8>]) -> Self {
+        OutUninit { buf, written: 0 }
     }
 
-    unsafe fn finish(self) -> [u8; N] {
-        let Self { data } = self;
-        // use data.array_assume_init() when stable
-        (data.as_ptr() as *const [u8; N]).read()
+    pub fn finish(self) -> &'b mut [u8] {
+        let OutUninit { buf, written } = self;
+        assert_eq!(written, buf.len(), "output not fully written");
+        // SAFETY: `exec` only ever writes the next `len` bytes after `written`,
+        // so reaching `buf.len()` means every byte has been initialized
+        unsafe { &mut *(buf as *mut [MaybeUninit<u8>] as *mut [u8]) }
     }
 }
 
-impl<const N: usize> IOBuf for OutUninitStack<N> {
+impl<'b> IOBuf for OutUninit<'b> {
     #[inline(always)]
     fn len(&self) -> usize {
-        N
+        self.buf.len()
     }
 
     #[inline(always)]
     fn exec(&mut self, buf_part: &mut [u8], iobuf_offset: usize, len: usize) {
-        unsafe {
-            copy_nonoverlapping(
-                buf_part.as_ptr(),
-                self.data.as_mut_ptr().cast::<u8>().add(iobuf_offset),
-                len,
-            );
+        assert_eq!(iobuf_offset, self.written, "output must be written in order");
+        for (dst, src) in self.buf[iobuf_offset..][..len].iter_mut().zip(&buf_part[..len]) {
+            let _ = dst.write(*src);
         }
+        self.written += len;
     }
 }
 
-pub trait SqueezeInitStack<const N: usize>: Foldable + Switch {
-    fn squeeze_to_array(&mut self) -> [u8; N] {
-        self.switch::<Squeezing>();
-        let mut out = OutUninitStack::new();
-        self.fold(&mut out);
-        unsafe { out.finish() }
-    }
-}
-
-// alloc::raw_vec::capacity_overflow
-#[cfg(feature = "alloc")]
-fn capacity_overflow() -> ! {
-    panic!("capacity overflow");
-}
-
-#[cfg(feature = "alloc")]
-fn alloc_bytes(len: usize) -> NonNull<[u8]> {
-    assert_ne!(len, 0);
-    let layout = Layout::array::<u8>(len).unwrap_or_else(|_| capacity_overflow());
-    // use <alloc::alloc::Global as core::alloc::Allocator>::alloc when stable
-    let raw_ptr = unsafe { alloc(layout) };
-    let ptr = NonNull::new(raw_ptr).unwrap_or_else(|| handle_alloc_error(layout));
-    NonNull::slice_from_raw_parts(ptr, len)
+// fallback for `Squeeze` implementors without a direct uninitialized path
+#[inline]
+pub(crate) fn zeroed(buf: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    buf.fill(MaybeUninit::new(0));
+    let len = buf.len();
+    let mut out = OutUninit::new(buf);
+    out.written = len;
+    out.finish()
 }
 
 #[cfg(feature = "alloc")]
-struct OutUninitHeap {
-    ptr: NonNull<[u8]>,
-}
-
-#[cfg(feature = "alloc")]
-impl OutUninitHeap {
-    fn new(len: usize) -> Self {
-        let ptr = alloc_bytes(len);
-        Self { ptr }
-    }
-
-    unsafe fn finish(self) -> Box<[u8]> {
-        let Self { mut ptr } = self;
-        Box::from_raw(ptr.as_mut())
-    }
-}
-
-#[cfg(feature = "alloc")]
-impl IOBuf for OutUninitHeap {
-    #[inline(always)]
-    fn len(&self) -> usize {
-        self.ptr.len()
-    }
-
-    #[inline(always)]
-    fn exec(&mut self, buf_part: &mut [u8], iobuf_offset: usize, len: usize) {
-        unsafe {
-            copy_nonoverlapping(
-                buf_part.as_ptr(),
-                self.ptr.as_ptr().cast::<u8>().add(iobuf_offset),
-                len,
-            );
-        }
-    }
-}
-
-#[cfg(feature = "alloc")]
-pub trait SqueezeInitHeap<const N: usize>: Foldable + Switch {
-    fn squeeze_to_box(&mut self, len: usize) -> Box<[u8]> {
-        self.switch::<Squeezing>();
-        let mut out = OutUninitHeap::new(len);
-        self.fold(&mut out);
-        unsafe { out.finish() }
-    }
+pub(crate) fn squeeze_to_box<S: crate::Squeeze + ?Sized>(ctx: &mut S, len: usize) -> alloc::boxed::Box<[u8]> {
+    let mut buf = alloc::boxed::Box::new_uninit_slice(len);
+    let out = ctx.squeeze_uninit(&mut buf);
+    // `squeeze_uninit` is safe to override, so check it really filled our buffer
+    assert!(out.len() == len && core::ptr::eq(out.as_ptr(), buf.as_ptr().cast()), "squeeze_uninit returned a foreign buffer");
+    // SAFETY: a `&mut [u8]` over the whole buffer exists, so all of it is initialized
+    unsafe { buf.assume_init() }
 }
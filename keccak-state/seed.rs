@@ -0,0 +1,16 @@
+use crate::Absorb;
+use core::mem::MaybeUninit;
+#[cfg(feature = "zeroize-on-drop")] use zeroize::Zeroize;
+
+// TODO merge to Absorb after Foldable complete
+pub trait AbsorbSeed: Absorb {
+    fn absorb_seed<const N: usize>(&mut self) {
+        let mut buf = [MaybeUninit::<u8>::uninit(); N];
+        let ready_buf = getrandom::fill_uninit(&mut buf).unwrap();
+        self.absorb(ready_buf);
+        #[cfg(feature = "zeroize-on-drop")]
+        ready_buf.zeroize();
+    }
+}
+
+impl<T: Absorb> AbsorbSeed for T {}
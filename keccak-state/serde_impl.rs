@@ -0,0 +1,162 @@
+use core::fmt;
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::{self, Visitor}};
+use crate::{KeccakState, Permutation, SNAPSHOT_LEN};
+
+// byte string that is hex in human-readable formats and raw bytes otherwise
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SerdeBytes<B>(pub B);
+
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+fn hex_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn hex_decode<E: de::Error>(s: &str, output: &mut [u8]) -> Result<(), E> {
+    let s = s.as_bytes();
+    if s.len() != output.len() * 2 {
+        return Err(E::invalid_length(s.len() / 2, &"hex string of the expected length"));
+    }
+    for (byte, pair) in output.iter_mut().zip(s.chunks_exact(2)) {
+        match (hex_nibble(pair[0]), hex_nibble(pair[1])) {
+            (Some(hi), Some(lo)) => *byte = (hi << 4) | lo,
+            _ => return Err(E::custom("invalid hex string")),
+        }
+    }
+    Ok(())
+}
+
+impl<B: AsRef<[u8]>> Serialize for SerdeBytes<B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Hex(self.0.as_ref()))
+        } else {
+            serializer.serialize_bytes(self.0.as_ref())
+        }
+    }
+}
+
+struct ArrayVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes or a hex string of them", N)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<[u8; N], E> {
+        let mut buf = [0; N];
+        hex_decode(v, &mut buf)?;
+        Ok(buf)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<[u8; N], E> {
+        v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for SerdeBytes<[u8; N]> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ArrayVisitor)
+        } else {
+            deserializer.deserialize_bytes(ArrayVisitor)
+        }.map(SerdeBytes)
+    }
+}
+
+// borrowing is only possible from raw bytes, hex strings always need a copy
+struct BorrowedVisitor;
+
+impl<'de> Visitor<'de> for BorrowedVisitor {
+    type Value = &'de [u8];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("borrowed bytes")
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<&'de [u8], E> {
+        Ok(v)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for SerdeBytes<&'a [u8]> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // the hex string itself would otherwise be borrowed as the bytes
+        if deserializer.is_human_readable() {
+            return Err(de::Error::custom("borrowed bytes cannot be read from a human-readable format"));
+        }
+        deserializer.deserialize_bytes(BorrowedVisitor).map(SerdeBytes)
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod owned {
+    use core::fmt;
+    use alloc::{boxed::Box, vec, vec::Vec};
+    use serde::{Deserialize, Deserializer, de::{self, Visitor}};
+    use super::{SerdeBytes, hex_decode};
+
+    struct BoxedVisitor;
+
+    impl<'de> Visitor<'de> for BoxedVisitor {
+        type Value = Box<[u8]>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("bytes or a hex string of them")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Box<[u8]>, E> {
+            let mut buf = vec![0; v.len() / 2];
+            hex_decode(v, &mut buf)?;
+            Ok(buf.into_boxed_slice())
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Box<[u8]>, E> {
+            Ok(v.into())
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Box<[u8]>, E> {
+            Ok(v.into_boxed_slice())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for SerdeBytes<Box<[u8]>> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(BoxedVisitor)
+            } else {
+                deserializer.deserialize_byte_buf(BoxedVisitor)
+            }.map(SerdeBytes)
+        }
+    }
+}
+
+// full state snapshot, see `KeccakState::export`
+impl<P: Permutation<Lane = u64>, const R: usize> Serialize for KeccakState<P, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerdeBytes(self.export()).serialize(serializer)
+    }
+}
+
+impl<'de, P: Permutation<Lane = u64>, const R: usize> Deserialize<'de> for KeccakState<P, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SerdeBytes(snapshot) = SerdeBytes::<[u8; SNAPSHOT_LEN]>::deserialize(deserializer)?;
+        KeccakState::import(&snapshot).ok_or_else(|| de::Error::custom("snapshot does not match this state type"))
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{KeccakState, KeccakF, Absorb, Squeeze, Reset, Foldable, Switch, IOBuf, DKeccak, DSHA3, DSHAKE, R128, R224, R256, R384, R512};
+
+#[derive(Clone)]
+pub struct FixedHash<const D: u8, const R: usize, const N: usize> {
+    ctx: KeccakState<KeccakF, R>,
+}
+
+impl<const D: u8, const R: usize, const N: usize> FixedHash<D, R, N> {
+    pub fn new() -> Self {
+        FixedHash { ctx: KeccakState::new(D) }
+    }
+
+    pub fn finalize_into(mut self, output: &mut [u8; N]) {
+        self.ctx.squeeze(output);
+    }
+
+    pub fn finalize(mut self) -> [u8; N] {
+        self.ctx.squeeze_to_array()
+    }
+
+    #[inline]
+    pub fn once(input: &[u8]) -> [u8; N] {
+        Self::new().chain_absorb(input).finalize()
+    }
+}
+
+impl<const D: u8, const R: usize, const N: usize> Default for FixedHash<D, R, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const D: u8, const R: usize, const N: usize> Absorb for FixedHash<D, R, N> {
+    #[inline(always)]
+    fn absorb(&mut self, input: &[u8]) {
+        self.ctx.absorb(input);
+    }
+}
+
+impl<const D: u8, const R: usize, const N: usize> Reset for FixedHash<D, R, N> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.ctx.reset();
+    }
+}
+
+pub type Sha3<const R: usize, const N: usize> = FixedHash<DSHA3, R, N>;
+
+pub type Sha3_224 = Sha3<R224, 28>;
+pub type Sha3_256 = Sha3<R256, 32>;
+pub type Sha3_384 = Sha3<R384, 48>;
+pub type Sha3_512 = Sha3<R512, 64>;
+
+// original Keccak submission padding, as used by Ethereum
+pub type Keccak<const R: usize, const N: usize> = FixedHash<DKeccak, R, N>;
+
+pub type Keccak224 = Keccak<R224, 28>;
+pub type Keccak256 = Keccak<R256, 32>;
+pub type Keccak384 = Keccak<R384, 48>;
+pub type Keccak512 = Keccak<R512, 64>;
+
+#[derive(Clone)]
+pub struct Shake<const R: usize> {
+    ctx: KeccakState<KeccakF, R>,
+}
+
+impl<const R: usize> Shake<R> {
+    pub fn new() -> Self {
+        Shake { ctx: KeccakState::new(DSHAKE) }
+    }
+
+    #[inline]
+    pub fn once(input: &[u8], output: &mut [u8]) {
+        Self::new().chain_absorb(input).squeeze(output)
+    }
+
+    #[inline]
+    pub fn once_to_array<const N: usize>(input: &[u8]) -> [u8; N] {
+        Self::new().chain_absorb(input).squeeze_to_array()
+    }
+}
+
+impl<const R: usize> Default for Shake<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const R: usize> Foldable for Shake<R> {
+    #[inline(always)]
+    fn fold<B: IOBuf>(&mut self, iobuf: &mut B) {
+        self.ctx.fold(iobuf)
+    }
+
+    #[inline(always)]
+    fn fill_block(&mut self) {
+        self.ctx.fill_block();
+    }
+}
+
+impl<const R: usize> Switch for Shake<R> {
+    #[inline(always)]
+    fn switch<const M: bool>(&mut self) {
+        self.ctx.switch::<M>()
+    }
+}
+
+impl<const R: usize> Reset for Shake<R> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.ctx.reset();
+    }
+}
+
+pub type Shake128 = Shake<R128>;
+pub type Shake256 = Shake<R256>;
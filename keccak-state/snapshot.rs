@@ -0,0 +1,51 @@
+use crate::{KeccakState, Permutation, lanes_to_bytes, BYTES, BITS, Absorbing, Squeezing};
+
+// layout (version 1):
+//   magic[4] version[1] rounds[1] rate[1] offset[1] delim[1] mode[1] state[200]
+// state is the little-endian lane bytes, as in `to_initial`
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"KCKS";
+pub const SNAPSHOT_VERSION: u8 = 1;
+const HEADER_LEN: usize = 10;
+pub const SNAPSHOT_LEN: usize = HEADER_LEN + BYTES(BITS);
+
+const MODE_ABSORBING: u8 = 0;
+const MODE_SQUEEZING: u8 = 1;
+
+impl<P: Permutation<Lane = u64>, const R: usize> KeccakState<P, R> {
+    pub fn export(&self) -> [u8; SNAPSHOT_LEN] {
+        let mut buf = [0; SNAPSHOT_LEN];
+        buf[..4].copy_from_slice(&SNAPSHOT_MAGIC);
+        buf[4] = SNAPSHOT_VERSION;
+        buf[5] = P::ROUNDS as u8;
+        buf[6] = R as u8;
+        buf[7] = self.offset as u8;
+        buf[8] = self.delim;
+        buf[9] = if self.mode == Absorbing { MODE_ABSORBING } else { MODE_SQUEEZING };
+        buf[HEADER_LEN..].copy_from_slice(&lanes_to_bytes(&self.lanes));
+        buf
+    }
+
+    // None if the snapshot is malformed or was taken from a different permutation or rate
+    pub fn import(buf: &[u8]) -> Option<Self> {
+        let buf: &[u8; SNAPSHOT_LEN] = buf.try_into().ok()?;
+        if buf[..4] != SNAPSHOT_MAGIC || buf[4] != SNAPSHOT_VERSION {
+            return None;
+        }
+        if buf[5] as usize != P::ROUNDS || buf[6] as usize != R {
+            return None;
+        }
+        let offset = buf[7] as usize;
+        if offset >= R {
+            return None;
+        }
+        let mode = match buf[9] {
+            MODE_ABSORBING => Absorbing,
+            MODE_SQUEEZING => Squeezing,
+            _ => return None,
+        };
+        let mut state = Self::with_initial(buf[8], buf[HEADER_LEN..].try_into().unwrap());
+        state.offset = offset;
+        state.mode = mode;
+        Some(state)
+    }
+}
@@ -0,0 +1,488 @@
+use hex_literal::hex;
+use crate::*;
+
+const A3_200: [u8; 200] = [0xa3; 200];
+
+#[test]
+fn test_sha3_224() {
+    assert_eq!(Sha3_224::once(b""), hex!("6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7"));
+    assert_eq!(Sha3_224::once(b"abc"), hex!("e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf"));
+    assert_eq!(Sha3_224::once(&A3_200), hex!("9376816aba503f72f96ce7eb65ac095deee3be4bf9bbc2a1cb7e11e0"));
+}
+
+#[test]
+fn test_sha3_256() {
+    assert_eq!(Sha3_256::once(b""), hex!("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"));
+    assert_eq!(Sha3_256::once(b"abc"), hex!("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"));
+    assert_eq!(Sha3_256::once(&A3_200), hex!("79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787"));
+}
+
+#[test]
+fn test_sha3_384() {
+    assert_eq!(Sha3_384::once(b""), hex!("0c63a75b845e4f7d01107d852e4c2485c51a50aaaa94fc61995e71bbee983a2ac3713831264adb47fb6bd1e058d5f004"));
+    assert_eq!(Sha3_384::once(b"abc"), hex!("ec01498288516fc926459f58e2c6ad8df9b473cb0fc08c2596da7cf0e49be4b298d88cea927ac7f539f1edf228376d25"));
+    assert_eq!(Sha3_384::once(&A3_200), hex!("1881de2ca7e41ef95dc4732b8f5f002b189cc1e42b74168ed1732649ce1dbcdd76197a31fd55ee989f2d7050dd473e8f"));
+}
+
+#[test]
+fn test_sha3_512() {
+    assert_eq!(Sha3_512::once(b""), hex!("a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26"));
+    assert_eq!(Sha3_512::once(b"abc"), hex!("b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0"));
+    assert_eq!(Sha3_512::once(&A3_200), hex!("e76dfad22084a8b1467fcf2ffa58361bec7628edf5f3fdc0e4805dc48caeeca81b7c13c30adf52a3659584739a2df46be589c51ca1a4a8416df6545a1ce8ba00"));
+}
+
+#[test]
+fn test_sha3_incremental() {
+    let mut ctx = Sha3_256::new();
+    for chunk in A3_200.chunks(7) {
+        ctx.absorb(chunk);
+    }
+    assert_eq!(ctx.clone().finalize(), Sha3_256::once(&A3_200));
+    ctx.reset();
+    assert_eq!(ctx.chain_absorb(b"abc").finalize(), Sha3_256::once(b"abc"));
+}
+
+#[test]
+fn test_shake128() {
+    assert_eq!(Shake128::once_to_array(b""), hex!("7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"));
+    assert_eq!(Shake128::once_to_array(b"abc"), hex!("5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8"));
+    assert_eq!(Shake128::once_to_array(&A3_200), hex!("131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037"));
+}
+
+#[test]
+fn test_shake256() {
+    assert_eq!(Shake256::once_to_array(b""), hex!("46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"));
+    assert_eq!(Shake256::once_to_array(b"abc"), hex!("483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4"));
+    assert_eq!(Shake256::once_to_array(&A3_200), hex!("cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b"));
+}
+
+#[test]
+fn test_shake_squeeze_ops() {
+    // last 32 bytes of 512-byte output
+    let mut ctx = Shake128::new().chain_absorb(&A3_200);
+    ctx.squeeze_skip(480);
+    assert_eq!(ctx.squeeze_to_array(), hex!("44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439"));
+    let mut ctx = Shake256::new().chain_absorb(&A3_200);
+    ctx.squeeze_skip(456);
+    let mut output = [0; 24];
+    ctx.squeeze(&mut output);
+    let mut xored = [0xff; 32];
+    ctx.squeeze_xor(&mut xored);
+    assert_eq!(xored.map(|b| !b), hex!("6a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb"));
+    ctx.reset();
+    assert_eq!(ctx.chain_absorb(b"abc").squeeze_to_array::<64>(), Shake256::once_to_array(b"abc"));
+}
+
+#[test]
+fn test_keccak224() {
+    assert_eq!(Keccak224::once(b""), hex!("f71837502ba8e10837bdd8d365adb85591895602fc552b48b7390abd"));
+    assert_eq!(Keccak224::once(b"abc"), hex!("c30411768506ebe1c2871b1ee2e87d38df342317300a9b97a95ec6a8"));
+    assert_eq!(Keccak224::once(&A3_200), hex!("42cc3f045bb950fcee6cba87ac0880296a1133936d620549901adbb7"));
+}
+
+#[test]
+fn test_keccak256() {
+    assert_eq!(Keccak256::once(b""), hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
+    assert_eq!(Keccak256::once(b"abc"), hex!("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"));
+    assert_eq!(Keccak256::once(&A3_200), hex!("3a57666b048777f2c953dc4456f45a2588e1cb6f2da760122d530ac2ce607d4a"));
+}
+
+#[test]
+fn test_keccak384() {
+    assert_eq!(Keccak384::once(b""), hex!("2c23146a63a29acf99e73b88f8c24eaa7dc60aa771780ccc006afbfa8fe2479b2dd2b21362337441ac12b515911957ff"));
+    assert_eq!(Keccak384::once(b"abc"), hex!("f7df1165f033337be098e7d288ad6a2f74409d7a60b49c36642218de161b1f99f8c681e4afaf31a34db29fb763e3c28e"));
+    assert_eq!(Keccak384::once(&A3_200), hex!("94026c78412d4739a463ec02ef157216ba9001e18d870c3575d69f17c77b21646e8dbc4e6436d207cec1785159bb7897"));
+}
+
+#[test]
+fn test_keccak512() {
+    assert_eq!(Keccak512::once(b""), hex!("0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e"));
+    assert_eq!(Keccak512::once(b"abc"), hex!("18587dc2ea106b9a1563e32b3312421ca164c7f1f07bc922a9c83d77cea3a1e5d0c69910739025372dc14ac9642629379540c17e2a65b19d77aa511a9d00bb96"));
+    assert_eq!(Keccak512::once(&A3_200), hex!("f4f846d140847539f53c3f082cc4e6810e143a5b4fc62a20597b5d76043246b86bd7149b906140bb9665a6ce83d991f032f2291d2fae80eedfc6f845cc16d5ae"));
+}
+
+fn ptn<const N: usize>() -> [u8; N] {
+    core::array::from_fn(|i| (i % 251) as u8)
+}
+
+// RFC 9861 section 5
+#[test]
+fn test_kangaroo_twelve() {
+    assert_eq!(KangarooTwelve::once_to_array(b"", b""), hex!("1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5"));
+    assert_eq!(KangarooTwelve::once_to_array(b"", b""), hex!("1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e54269c056b8c82e48276038b6d292966cc07a3d4645272e31ff38508139eb0a71"));
+    let mut xof = KangarooTwelve::new().finalize();
+    xof.squeeze_skip(10000);
+    assert_eq!(xof.squeeze_to_array(), hex!("e8dc563642f7228c84684c898405d3a834799158c079b12880277a1d28e2ff6d"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<1>(), b""), hex!("2bda92450e8b147f8a7cb629e784a058efca7cf7d8218e02d345dfaa65244a1f"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<17>(), b""), hex!("6bf75fa2239198db4772e36478f8e19b0f371205f6a9a93a273f51df37122888"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<289>(), b""), hex!("0c315ebcdedbf61426de7dcf8fb725d1e74675d7f5327a5067f367b108ecb67c"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<4913>(), b""), hex!("cb552e2ec77d9910701d578b457ddf772c12e322e4ee7fe417f92c758f0d59d0"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<83521>(), b""), hex!("8701045e22205345ff4dda05555cbb5c3af1a771c2b89baef37db43d9998b9fe"));
+    assert_eq!(KangarooTwelve::once_to_array(b"", &ptn::<1>()), hex!("fab658db63e94a246188bf7af69a133045f46ee984c56e3c3328caaf1aa1a583"));
+    assert_eq!(KangarooTwelve::once_to_array(b"\xff", &ptn::<41>()), hex!("d848c5068ced736f4462159b9867fd4c20b808acc3d5bc48e0b06ba0a3762ec4"));
+    assert_eq!(KangarooTwelve::once_to_array(b"\xff\xff\xff", &ptn::<1681>()), hex!("c389e5009ae57120854c2e8c64670ac01358cf4c1baf89447a724234dc7ced74"));
+    assert_eq!(KangarooTwelve::once_to_array(b"\xff\xff\xff\xff\xff\xff\xff", &ptn::<68921>()), hex!("75d2f86a2e644566726b4fbcfc5657b9dbcf070c7b0dca06450ab291d7443bcf"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<8191>(), b""), hex!("1b577636f723643e990cc7d6a659837436fd6a103626600eb8301cd1dbe553d6"));
+    assert_eq!(KangarooTwelve::once_to_array(&ptn::<8192>(), b""), hex!("48f256f6772f9edfb6a8b661ec92dc93b95ebd05a08a17b39ae3490870c926c3"));
+}
+
+#[test]
+fn test_kangaroo_twelve_streaming() {
+    let input = ptn::<20000>();
+    let mut ctx = KangarooTwelve::new();
+    for chunk in input.chunks(1000) {
+        ctx.absorb(chunk);
+    }
+    assert_eq!(ctx.finalize_custom(&ptn::<68921>()).squeeze_to_array(), hex!("0035ccfa8eedffcd226ab39ec9967e3fe130b171fd5a3d3c515f46ca0fb65b66ec397bed4ad9e2ec4f0629fba0e26130afa99e2b428b2752c6be18b04e9b2fcc"));
+}
+
+// not covered by RFC 9861, regression values produced by this implementation rather than published test vectors
+#[test]
+fn test_marsupilami_fourteen() {
+    assert_eq!(MarsupilamiFourteen::once_to_array(b"", b""), hex!("6f66ef1474eb53807aa329257c768bb88893d9f086e51da2f5c80d17ca0fc57d5a24fac879014f8b30a3fdf5ac56ebafa219eb891d4bbbab7e1df3b27205b459"));
+    assert_eq!(MarsupilamiFourteen::once_to_array(&ptn::<17>(), b""), hex!("aa764fd8b38f19976a305cb007f19384b210a5c7b0fc4499d6f83c6227bff850"));
+    assert_eq!(MarsupilamiFourteen::once_to_array(&ptn::<4913>(), &ptn::<41>()), hex!("39070c904b9643a646e19f214b83572494f40cb6494186f5ee90440fd678a1260891d557a5b608ffe4dc445770ec45345fc5f954eb7ad21acb856e4ce5c2103c"));
+    assert_eq!(MarsupilamiFourteen::once_to_array(&ptn::<83521>(), b""), hex!("35af0a5fc6c4d111fbc68f879d05506aafd300b5ab136986d7aed8a9f1be331e"));
+}
+
+// RFC 9861 section 5
+#[test]
+fn test_turboshake128() {
+    assert_eq!(TurboShake128::once_to_array(b"", DTurboSHAKE), hex!("1e415f1c5983aff2169217277d17bb538cd945a397ddec541f1ce41af2c1b74c3e8ccae2a4dae56c84a04c2385c03c15e8193bdf58737363321691c05462c8df"));
+    let mut ctx = TurboShake128::default();
+    ctx.squeeze_skip(10000);
+    assert_eq!(ctx.squeeze_to_array(), hex!("a3b9b0385900ce761f22aed548e754da10a5242d62e8c658e3f3a923a7555607"));
+    assert_eq!(TurboShake128::once_to_array(&ptn::<1>(), DTurboSHAKE), hex!("55cedd6f60af7bb29a4042ae832ef3f58db7299f893ebb9247247d856958daa9"));
+    assert_eq!(TurboShake128::once_to_array(&ptn::<17>(), DTurboSHAKE), hex!("9c97d036a3bac819db70ede0ca554ec6e4c2a1a4ffbfd9ec269ca6a111161233"));
+    assert_eq!(TurboShake128::once_to_array(&ptn::<289>(), DTurboSHAKE), hex!("96c77c279e0126f7fc07c9b07f5cdae1e0be60bdbe10620040e75d7223a624d2"));
+    assert_eq!(TurboShake128::once_to_array(&ptn::<83521>(), DTurboSHAKE), hex!("da67c7039e98bf530cf7a37830c6664e14cbab7f540f58403b1b82951318ee5c"));
+    assert_eq!(TurboShake128::once_to_array(b"\xff\xff\xff", 0x01), hex!("bf323f940494e88ee1c540fe660be8a0c93f43d15ec006998462fa994eed5dab"));
+    assert_eq!(TurboShake128::once_to_array(b"\xff", 0x06), hex!("8ec9c66465ed0d4a6c35d13506718d687a25cb05c74cca1e42501abd83874a67"));
+    assert_eq!(TurboShake128::once_to_array(b"\xff\xff\xff", 0x07), hex!("b658576001cad9b1e5f399a9f77723bba05458042d68206f7252682dba3663ed"));
+    assert_eq!(TurboShake128::once_to_array(b"\xff\xff\xff\xff\xff\xff\xff", 0x0b), hex!("8deeaa1aec47ccee569f659c21dfa8e112db3cee37b18178b2acd805b799cc37"));
+    assert_eq!(TurboShake128::once_to_array(b"\xff", 0x30), hex!("553122e2135e363c3292bed2c6421fa232bab03daa07c7d6636603286506325b"));
+    assert_eq!(TurboShake128::once_to_array(b"\xff\xff\xff", 0x7f), hex!("16274cc656d44cefd422395d0f9053bda6d28e122aba15c765e5ad0e6eaf26f9"));
+}
+
+#[test]
+fn test_turboshake256() {
+    assert_eq!(TurboShake256::once_to_array(b"", DTurboSHAKE), hex!("367a329dafea871c7802ec67f905ae13c57695dc2c6663c61035f59a18f8e7db11edc0e12e91ea60eb6b32df06dd7f002fbafabb6e13ec1cc20d995547600db0"));
+    assert_eq!(TurboShake256::once_to_array(&ptn::<289>(), DTurboSHAKE), hex!("66b810db8e90780424c0847372fdc95710882fde31c6df75beb9d4cd9305cfcae35e7b83e8b7e6eb4b78605880116316fe2c078a09b94ad7b8213c0a738b65c0"));
+    assert_eq!(TurboShake256::once_to_array(b"\xff\xff\xff", 0x7f), hex!("abe569c1f77ec340f02705e7d37c9ab7e155516e4a6a150021d70b6fac0bb40c069f9a9828a0d575cd99f9bae435ab1acf7ed9110ba97ce0388d074bac768776"));
+}
+
+#[test]
+fn test_turboshake_domain() {
+    assert!(TurboShake128::try_new(0x00).is_none());
+    assert!(TurboShake128::try_new(0x80).is_none());
+    assert!(TurboShake256::try_new(0xff).is_none());
+    assert!(TurboShake256::try_new(0x01).is_some());
+}
+
+#[test]
+fn test_initial_roundtrip() {
+    let initial: [u8; 200] = core::array::from_fn(|i| i as u8);
+    let ctx = KeccakState::<KeccakF, R256>::with_initial(DSHAKE, initial);
+    assert_eq!(ctx.to_initial(), Some(initial));
+    let ctx = KeccakState::<KeccakF, R256>::with_initial(DSHAKE, initial).chain_absorb(b"abc");
+    assert_eq!(ctx.to_initial(), None);
+}
+
+#[test]
+fn test_squeeze_uninit() {
+    use core::mem::MaybeUninit;
+    let mut buf = [MaybeUninit::uninit(); 300];
+    let output = Shake128::new().chain_absorb(b"abc").squeeze_uninit(&mut buf);
+    let mut expected = [0; 300];
+    Shake128::once(b"abc", &mut expected);
+    assert_eq!(output, &expected);
+    let mut buf = [MaybeUninit::uninit(); 64];
+    let mut xof = KangarooTwelve::new().finalize();
+    let output = xof.squeeze_uninit(&mut buf[..32]);
+    assert_eq!(output, &hex!("1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_squeeze_to_box() {
+    let mut ctx = Shake256::new().chain_absorb(&A3_200);
+    assert!(ctx.squeeze_to_box(0).is_empty());
+    assert_eq!(&*ctx.squeeze_to_box(64), &hex!("cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d2d700caae7396ece96604440577da4f3aa22aeb8857f961c4cd8e06f0ae6610b"));
+    let output = Shake256::new().chain_absorb(&A3_200).squeeze_to_vec(512);
+    assert_eq!(&output[480..], &hex!("6a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb"));
+}
+
+#[cfg(feature = "seed")]
+#[test]
+fn test_absorb_seed() {
+    let mut a = Shake256::new();
+    let mut b = a.clone();
+    a.absorb_seed::<32>();
+    b.absorb_seed::<32>();
+    assert_ne!(a.squeeze_to_array::<32>(), b.squeeze_to_array::<32>());
+}
+
+#[test]
+fn test_snapshot() {
+    let mut ctx = KeccakState::<KeccakF, R256>::new(DSHAKE);
+    ctx.absorb(&A3_200[..150]);
+    let snapshot = ctx.export();
+    let mut resumed = KeccakState::<KeccakF, R256>::import(&snapshot).unwrap();
+    resumed.absorb(&A3_200[150..]);
+    let mut expected = [0; 300];
+    Shake256::once(&A3_200, &mut expected);
+    assert_eq!(resumed.squeeze_to_array::<100>(), expected[..100]);
+    let snapshot = resumed.export();
+    let mut resumed = KeccakState::<KeccakF, R256>::import(&snapshot).unwrap();
+    assert_eq!(resumed.squeeze_to_array::<200>(), expected[100..]);
+}
+
+#[test]
+fn test_snapshot_mismatch() {
+    let mut ctx = KeccakState::<KeccakF, R256>::new(DSHAKE);
+    ctx.absorb(&A3_200);
+    let snapshot = ctx.export();
+    assert!(KeccakState::<KeccakF, R128>::import(&snapshot).is_none());
+    assert!(KeccakState::<KeccakP, R256>::import(&snapshot).is_none());
+    assert!(KeccakState::<KeccakF, R256>::import(&snapshot[..SNAPSHOT_LEN - 1]).is_none());
+    for (i, byte) in [(0, b'X'), (4, SNAPSHOT_VERSION + 1), (7, R256 as u8), (9, 2)] {
+        let mut bad = snapshot;
+        bad[i] = byte;
+        assert!(KeccakState::<KeccakF, R256>::import(&bad).is_none());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_snapshot() {
+    let mut ctx = KeccakState::<KeccakF, R256>::new(DSHAKE);
+    ctx.absorb(&A3_200[..150]);
+    let json = serde_json::to_string(&ctx).unwrap();
+    assert!(json.starts_with("\"4b434b530118880e1f00"));
+    assert_eq!(json.len(), 2 + SNAPSHOT_LEN * 2);
+    let resumed: KeccakState<KeccakF, R256> = serde_json::from_str(&json).unwrap();
+    assert_eq!(resumed.export(), ctx.export());
+    let bytes = postcard::to_allocvec(&ctx).unwrap();
+    let resumed: KeccakState<KeccakF, R256> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(resumed.export(), ctx.export());
+    assert!(serde_json::from_str::<KeccakState<KeccakP, R256>>(&json).is_err());
+    assert!(serde_json::from_str::<KeccakState<KeccakF, R256>>("\"4b434b\"").is_err());
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_digest() {
+    use digest::{Digest, ExtendableOutput, ExtendableOutputReset, XofReader, FixedOutput, FixedOutputReset, Update};
+    fn hash<D: Digest>(input: &[u8]) -> digest::Output<D> {
+        D::new().chain_update(input).finalize()
+    }
+    assert_eq!(hash::<Sha3_256>(b"abc")[..], hex!("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"));
+    assert_eq!(hash::<Keccak256>(b"")[..], hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"));
+    let mut ctx = Sha3_512::new();
+    Update::update(&mut ctx, &A3_200);
+    assert_eq!(ctx.finalize_fixed_reset()[..], Sha3_512::once(&A3_200));
+    assert_eq!(FixedOutput::finalize_fixed(ctx)[..], Sha3_512::once(b""));
+
+    let mut expected = [0; 512];
+    Shake256::once(&A3_200, &mut expected);
+    let mut ctx = Shake256::default();
+    Update::update(&mut ctx, &A3_200);
+    let mut reader = ctx.finalize_xof_reset();
+    let mut output = [0; 512];
+    reader.read(&mut output[..100]);
+    reader.read(&mut output[100..]);
+    assert_eq!(output, expected);
+    ctx.finalize_xof().read(&mut output[..4]);
+    assert_eq!(output[..4], Shake256::once_to_array::<4>(b""));
+}
+
+#[test]
+fn test_backends() {
+    let available = || Backend::ALL.into_iter().filter(|backend| backend.is_available());
+    // lanes of Keccak-f[1600] applied to the zero state
+    for backend in available() {
+        let mut lanes = [0; 25];
+        backend.permute(&mut lanes, 24);
+        assert_eq!(lanes[..5], [0xF1258F7940E1DDE7, 0x84D5CCF933C0478A, 0xD598261EA65AA9EE, 0xBD1547306F80494D, 0x8B284E056253D057], "{:?}", backend);
+    }
+    let start: [u64; 25] = core::array::from_fn(|i| ((i % 5 * 7 + i / 5 * 13 + 1) as u64).wrapping_mul(0x0123456789ABCDEF));
+    for (rounds, first, last) in [(1, 0x1721777B90758409, 0x1FC6CC7FC32C1B87), (12, 0xB89EE6B515884AD9, 0x1FB01A2C310B63ED), (24, 0x18A8ECFC06E45884, 0x24E9168924CFD69B)] {
+        for backend in available() {
+            let mut lanes = start;
+            backend.permute(&mut lanes, rounds);
+            assert_eq!((lanes[0], lanes[24]), (first, last), "{:?} with {} rounds", backend, rounds);
+        }
+    }
+    // cross-check every round count on a chain of states
+    let mut expected = start;
+    let mut actual = [start; Backend::ALL.len()];
+    for rounds in (0..=24).chain(0..=24) {
+        Backend::Reference.permute(&mut expected, rounds);
+        for (backend, lanes) in Backend::ALL.into_iter().zip(&mut actual).skip(1) {
+            if backend.is_available() {
+                backend.permute(lanes, rounds);
+                assert_eq!(*lanes, expected, "{:?} with {} rounds", backend, rounds);
+            }
+        }
+    }
+}
+
+fn multi_matches_single<P: Permutation<Lane = u64>, const R: usize, const N: usize>() {
+    let mut multi = KeccakStateX::<P, R, N>::new(DSHAKE);
+    let mut single: [KeccakState<P, R>; N] = core::array::from_fn(|_| KeccakState::new(DSHAKE));
+    let inputs: [[u8; 1000]; N] = core::array::from_fn(|k| core::array::from_fn(|i| (i * 7 + k * 31) as u8));
+    for (start, end) in [(0, 0), (0, 3), (3, R), (R, R + 1), (R + 1, 3 * R + 5), (3 * R + 5, 1000)] {
+        multi.absorb(inputs.each_ref().map(|i| &i[start..end]));
+        for (state, input) in single.iter_mut().zip(&inputs) {
+            state.absorb(&input[start..end]);
+        }
+    }
+    let mut expected = [[0; 700]; N];
+    for (state, output) in single.iter_mut().zip(&mut expected) {
+        state.squeeze(output);
+    }
+    let mut actual = [[0; 700]; N];
+    for (start, end) in [(0, 1), (1, R - 1), (R - 1, 2 * R), (2 * R, 600)] {
+        multi.squeeze(actual.each_mut().map(|o| &mut o[start..end]));
+    }
+    let mut states = multi.into_states();
+    for (state, output) in states.iter_mut().zip(&mut actual) {
+        state.squeeze(&mut output[600..]);
+    }
+    assert_eq!(actual, expected);
+    let mut multi = KeccakStateX::from_states(states).unwrap();
+    let next = multi.squeeze_to_arrays::<32>();
+    for (state, next) in single.iter_mut().zip(next) {
+        assert_eq!(state.squeeze_to_array::<32>(), next);
+    }
+}
+
+#[test]
+fn test_multi() {
+    multi_matches_single::<KeccakF, R256, 2>();
+    multi_matches_single::<KeccakF, R128, 3>();
+    multi_matches_single::<KeccakF, R128, 4>();
+    multi_matches_single::<KeccakP, R128, 4>();
+    multi_matches_single::<KeccakF, R512, 8>();
+    let states = [KeccakState::<KeccakF, R256>::new(DSHAKE), KeccakState::new(DSHAKE).chain_absorb(b"abc")];
+    assert!(KeccakStateX2::from_states(states).is_none());
+}
+
+#[test]
+fn test_reduced_rounds() {
+    let _: KeccakState<KeccakRounds<24>, R256> = KeccakState::<KeccakF, R256>::new(DSHAKE);
+    for rounds in [1, 4, 14, 23] {
+        let mut lanes = [0; 25];
+        lanes[0] = DSHAKE as u64;
+        lanes[R256 / 8 - 1] = 0x80 << 56;
+        Backend::Reference.permute(&mut lanes, rounds);
+        let expected: [u8; 32] = core::array::from_fn(|i| lanes[i / 8].to_le_bytes()[i % 8]);
+        let actual = match rounds {
+            1 => KeccakState::<KeccakRounds<1>, R256>::new(DSHAKE).squeeze_to_array(),
+            4 => KeccakState::<KeccakRounds<4>, R256>::new(DSHAKE).squeeze_to_array(),
+            14 => KeccakState::<KeccakP14, R256>::new(DSHAKE).squeeze_to_array(),
+            _ => KeccakState::<KeccakRounds<23>, R256>::new(DSHAKE).squeeze_to_array(),
+        };
+        assert_eq!(actual, expected, "{} rounds", rounds);
+    }
+}
+
+#[test]
+fn test_narrow_widths() {
+    // Keccak-f applied once and twice to the zero state, from XKCP
+    // tests/TestVectors/KeccakF-{800,400,200}-IntermediateValues.txt
+    fn permute_twice<L: Lane + PartialEq + core::fmt::Debug>(first: [L; 25], second: [L; 25]) {
+        let mut lanes = [L::default(); 25];
+        L::permute(&mut lanes, L::ROUNDS);
+        assert_eq!(lanes, first);
+        L::permute(&mut lanes, L::ROUNDS);
+        assert_eq!(lanes, second);
+    }
+    permute_twice::<u32>([
+        0xE531D45D, 0xF404C6FB, 0x23A0BF99, 0xF1F8452F, 0x51FFD042, 0xE539F578, 0xF00B80A7,
+        0xAF973664, 0xBF5AF34C, 0x227A2424, 0x88172715, 0x9F685884, 0xB15CD054, 0x1BF4FC0E,
+        0x6166FA91, 0x1A9E599A, 0xA3970A1F, 0xAB659687, 0xAFAB8D68, 0xE74B1015, 0x34001A98,
+        0x4119EFF3, 0x930A0E76, 0x87B28070, 0x11EFE996,
+    ], [
+        0x75BF2D0D, 0x9B610E89, 0xC826AF40, 0x64CD84AB, 0xF905BDD6, 0xBC832835, 0x5F8001B9,
+        0x15662CCE, 0x8E38C95E, 0x701FE543, 0x1B544380, 0x89ACDEFF, 0x51EDB5DE, 0x0E9702D9,
+        0x6C19AA16, 0xA2913EEE, 0x60754E9A, 0x9819063C, 0xF4709254, 0xD09F9084, 0x772DA259,
+        0x1DB35DF7, 0x5AA60162, 0x358825D5, 0xB3783BAB,
+    ]);
+    permute_twice::<u16>([
+        0x09F5, 0x40AC, 0x0FA9, 0x14F5, 0xE89F, 0xECA0, 0x5BD1, 0x7870, 0xEFF0, 0xBF8F, 0x0337,
+        0x6052, 0xDC75, 0x0EC9, 0xE776, 0x5246, 0x59A1, 0x5D81, 0x6D95, 0x6E14, 0x633E, 0x58EE,
+        0x71FF, 0x714C, 0xB38E,
+    ], [
+        0xE537, 0xD5D6, 0xDBE7, 0xAAF3, 0x9BC7, 0xCA7D, 0x86B2, 0xFDEC, 0x692C, 0x4E5B, 0x67B1,
+        0x15AD, 0xA7F7, 0xA66F, 0x67FF, 0x3F8A, 0x2F99, 0xE2C2, 0x656B, 0x5F31, 0x5BA6, 0xCA29,
+        0xC224, 0xB85C, 0x097C,
+    ]);
+    permute_twice::<u8>([
+        0x3C, 0x28, 0x26, 0x84, 0x1C, 0xB3, 0x5C, 0x17, 0x1E, 0xAA, 0xE9, 0xB8, 0x11, 0x13,
+        0x4C, 0xEA, 0xA3, 0x85, 0x2C, 0x69, 0xD2, 0xC5, 0xAB, 0xAF, 0xEA,
+    ], [
+        0x1B, 0xEF, 0x68, 0x94, 0x92, 0xA8, 0xA5, 0x43, 0xA5, 0x99, 0x9F, 0xDB, 0x83, 0x4E,
+        0x31, 0x66, 0xA1, 0x4B, 0xE8, 0x27, 0xD9, 0x50, 0x40, 0x47, 0x9E,
+    ]);
+
+    fn sponge<P: Permutation, const R: usize>() -> [u8; 48] {
+        let input: [u8; 150] = core::array::from_fn(|i| (i * 7) as u8);
+        let mut state = KeccakState::<P, R>::new(DSHAKE);
+        state.absorb(&input[..100]);
+        state.absorb(&input[100..]);
+        state.squeeze_to_array()
+    }
+    assert_eq!(KeccakState::<KeccakF800, 68>::STATE_BYTES, 100);
+    assert_eq!(sponge::<KeccakF800, 68>(), hex!("23ad324da96f87234813194f41683e564a6dfd1c559789bd69b419aa9a1fd6d12264f59360783118f8f342f708b8f096"));
+    assert_eq!(sponge::<KeccakRounds<12, u32>, 68>(), hex!("86b9d12a24c84e19293481231629677295b44c9f66686473e50b4a964998cca14fc3650f4cc3a8a3b8dfcc2c85af7107"));
+    assert_eq!(sponge::<KeccakF400, 34>(), hex!("33e208fdd6a6afa9ca4390e3f81993ea3896181a1208064fabebecc7faa481f3f8fec77aac6578fedaf76ff0d4f47503"));
+    assert_eq!(sponge::<KeccakRounds<12, u16>, 34>(), hex!("1c9daf67e8822f8b9fd43936ce93fbfee795caf77060cfc22499cda12a27f2ffd867d45618f0eefd9046716a44566476"));
+    assert_eq!(sponge::<KeccakF200, 9>(), hex!("c76160d32ed8592c26141938e285f1a33178f8899b11ff850d1c034d2ec95c618de790db7d59f3b02033b00403f4b4f5"));
+}
+
+#[test]
+fn test_security_levels() {
+    assert_eq!((Sec128::RATE, Sec224::RATE, Sec256::RATE, Sec384::RATE, Sec512::RATE), (R128, R224, R256, R384, R512));
+    assert_eq!(Sec256::CAPACITY, 64);
+    assert_eq!((Sec128::rate::<u32>(), Sec128::rate::<u16>()), (68, 18));
+    let mut state = KeccakState::<KeccakF, { Sec256::RATE }>::new(DSHAKE);
+    state.absorb(b"abc");
+    assert_eq!(state.squeeze_to_array::<32>(), Shake256::once_to_array::<32>(b"abc"));
+}
+
+#[test]
+fn test_const_state() {
+    const ZERO: [u64; 25] = {
+        let mut lanes = [0; 25];
+        keccak_f1600(&mut lanes);
+        lanes
+    };
+    const SHA3: [u8; 32] = ConstKeccakState::<R256>::new(DSHA3).chain_absorb(b"abc").squeeze_to_array();
+    const SHAKE: [u8; 300] = {
+        let mut state = ConstKeccakState::<R128>::new(DSHAKE).chain_absorb(&A3_200);
+        state.absorb(&A3_200);
+        state.squeeze_to_array()
+    };
+    assert_eq!(ZERO[..5], [0xF1258F7940E1DDE7, 0x84D5CCF933C0478A, 0xD598261EA65AA9EE, 0xBD1547306F80494D, 0x8B284E056253D057]);
+    assert_eq!(SHA3, Sha3_256::once(b"abc"));
+    let mut shake = Shake128::new();
+    shake.absorb(&A3_200);
+    shake.absorb(&A3_200);
+    assert_eq!(SHAKE, shake.squeeze_to_array());
+    let mut lanes = ZERO;
+    keccak_p1600(&mut lanes, 12);
+    let mut expected = ZERO;
+    Backend::Complement.permute(&mut expected, 12);
+    assert_eq!(lanes, expected);
+}
+
+#[test]
+// trybuild spawns cargo, which Miri's isolation does not allow
+#[cfg_attr(miri, ignore)]
+fn test_compile_fail() {
+    let t = trybuild::TestCases::new();
+    // a passing case makes trybuild build instead of check, which the post-monomorphization errors need
+    t.pass("ui/valid.rs");
+    t.compile_fail("ui/invalid/*.rs");
+}
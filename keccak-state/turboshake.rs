@@ -0,0 +1,68 @@
+use crate::{KeccakState, KeccakP, Absorb, Squeeze, Reset, Foldable, Switch, IOBuf, R128, R256};
+
+pub const DTurboSHAKE: u8 = 0x1f;
+
+#[inline]
+const fn is_valid_domain(domain: u8) -> bool {
+    matches!(domain, 0x01..=0x7f)
+}
+
+#[derive(Clone)]
+pub struct TurboShake<const R: usize> {
+    ctx: KeccakState<KeccakP, R>,
+}
+
+impl<const R: usize> TurboShake<R> {
+    pub fn try_new(domain: u8) -> Option<Self> {
+        is_valid_domain(domain).then(|| TurboShake { ctx: KeccakState::new(domain) })
+    }
+
+    pub fn new(domain: u8) -> Self {
+        Self::try_new(domain).expect("domain separation byte must be in 0x01..=0x7F")
+    }
+
+    #[inline]
+    pub fn once(input: &[u8], domain: u8, output: &mut [u8]) {
+        Self::new(domain).chain_absorb(input).squeeze(output)
+    }
+
+    #[inline]
+    pub fn once_to_array<const N: usize>(input: &[u8], domain: u8) -> [u8; N] {
+        Self::new(domain).chain_absorb(input).squeeze_to_array()
+    }
+}
+
+impl<const R: usize> Default for TurboShake<R> {
+    fn default() -> Self {
+        Self::new(DTurboSHAKE)
+    }
+}
+
+impl<const R: usize> Foldable for TurboShake<R> {
+    #[inline(always)]
+    fn fold<B: IOBuf>(&mut self, iobuf: &mut B) {
+        self.ctx.fold(iobuf)
+    }
+
+    #[inline(always)]
+    fn fill_block(&mut self) {
+        self.ctx.fill_block();
+    }
+}
+
+impl<const R: usize> Switch for TurboShake<R> {
+    #[inline(always)]
+    fn switch<const M: bool>(&mut self) {
+        self.ctx.switch::<M>()
+    }
+}
+
+impl<const R: usize> Reset for TurboShake<R> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.ctx.reset();
+    }
+}
+
+pub type TurboShake128 = TurboShake<R128>;
+pub type TurboShake256 = TurboShake<R256>;
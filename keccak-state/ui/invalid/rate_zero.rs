@@ -0,0 +1,5 @@
+use keccak_state::{KeccakState, KeccakF, DSHAKE};
+
+fn main() {
+    let _ = KeccakState::<KeccakF, 0>::new(DSHAKE);
+}
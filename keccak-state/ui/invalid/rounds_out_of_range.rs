@@ -0,0 +1,5 @@
+use keccak_state::{KeccakState, KeccakRounds, R256, DSHAKE};
+
+fn main() {
+    let _ = KeccakState::<KeccakRounds<25>, R256>::new(DSHAKE);
+}
@@ -0,0 +1,7 @@
+use keccak_state::R;
+
+const RATE: usize = R(100);
+
+fn main() {
+    let _ = RATE;
+}
@@ -0,0 +1,5 @@
+use keccak_state::Sec512;
+
+fn main() {
+    let _ = Sec512::rate::<u8>();
+}
@@ -0,0 +1,11 @@
+use keccak_state::{KeccakState, KeccakRounds, KeccakF, KeccakF800, Sec128, R, R256, DSHAKE};
+
+const RATE: usize = R(544);
+
+fn main() {
+    let _ = KeccakState::<KeccakF, 8>::new(DSHAKE);
+    let _ = KeccakState::<KeccakF, 192>::new(DSHAKE);
+    let _ = KeccakState::<KeccakRounds<1>, R256>::new(DSHAKE);
+    let _ = KeccakState::<KeccakF, RATE>::new(DSHAKE);
+    let _ = KeccakState::<KeccakF800, { Sec128::rate::<u32>() }>::new(DSHAKE);
+}